@@ -0,0 +1,124 @@
+// External imports
+use image::gif::{GifEncoder, Repeat};
+use image::{Delay, DynamicImage, Frame, ImageResult, Rgb};
+use rand::thread_rng;
+
+// Standard imports
+use std::fs::File;
+use std::path::Path;
+
+// Self imports
+use crate::{Generator, RectGrid, Step};
+
+/// Options for recording the generation of a maze as an animated GIF.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// Width and height of each Cell in pixels.
+    pub cell_size: usize,
+
+    /// Time each frame is shown for, in milliseconds.
+    pub delay: u32,
+
+    /// Number of Steps taken between frames.
+    pub steps_per_frame: usize,
+
+    /// Colour of the current Cell. Cells that have been visited but not backtracked from are drawn in a paler version of it.
+    pub highlight: [u8; 3],
+
+    /// Time the finished maze is shown for before the GIF loops, in milliseconds.
+    pub final_delay: u32,
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation {
+            cell_size: 10,
+            delay: 20,
+            steps_per_frame: 1,
+            highlight: [230, 60, 60],
+            final_delay: 2000,
+        }
+    }
+}
+
+impl Animation {
+    /// Generate a maze in 'maze' with the Generator, recording a frame every 'steps_per_frame' Steps, and save it as a GIF at 'path'.
+    pub fn save<P: AsRef<Path>>(
+        &self,
+        generator: &dyn Generator,
+        maze: &mut RectGrid,
+        path: P,
+    ) -> ImageResult<()> {
+        let mut encoder = GifEncoder::new(File::create(path)?);
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        // Cells that have been visited but not backtracked from, and the most recently visited Cell.
+        let mut active = vec![false; maze.size()];
+        let mut current = None;
+
+        // The Generator carves 'maze' directly while every Step is replayed onto a copy that each frame is drawn from,
+        // so frames are encoded as the Steps happen and none of them need to be stored.
+        let mut shadow = maze.clone();
+        encoder.encode_frame(self.frame(&shadow, &active, current, self.delay))?;
+
+        let steps_per_frame = self.steps_per_frame.max(1);
+        let mut taken = 0;
+        let mut result = Ok(());
+        generator.gen_with_events(maze, &mut thread_rng(), &mut |step| {
+            step.apply(&mut shadow);
+            match step {
+                Step::Visit(index) => {
+                    active[index] = true;
+                    current = Some(index);
+                }
+                Step::Backtrack(index) => active[index] = false,
+                Step::Link(..) | Step::Unlink(..) => {}
+            }
+
+            // Stop encoding after the first error, the Generator can't be interrupted.
+            taken += 1;
+            if taken % steps_per_frame == 0 && result.is_ok() {
+                result = encoder.encode_frame(self.frame(&shadow, &active, current, self.delay));
+            }
+        });
+        result?;
+
+        // Show the finished maze without any highlighting.
+        let active = vec![false; active.len()];
+        encoder.encode_frame(self.frame(maze, &active, None, self.final_delay))
+    }
+
+    /// Draw a single frame of the animation.
+    fn frame(&self, maze: &RectGrid, active: &[bool], current: Option<usize>, delay: u32) -> Frame {
+        let [r, g, b] = self.highlight;
+        let highlight = Rgb([r, g, b]);
+        let pale = Rgb([pale(r), pale(g), pale(b)]);
+
+        let img = maze.draw(self.cell_size, |index| {
+            if current == Some(index) {
+                Some(highlight)
+            } else if active[index] {
+                Some(pale)
+            } else {
+                None
+            }
+        });
+
+        let buffer = DynamicImage::ImageRgb8(img).into_rgba8();
+        Frame::from_parts(buffer, 0, 0, Delay::from_numer_denom_ms(delay, 1))
+    }
+}
+
+/// Mix a colour channel with white.
+fn pale(channel: u8) -> u8 {
+    ((channel as u16 + 2 * 255) / 3) as u8
+}
+
+/// Generate a maze in 'maze' with the Generator and save the process as an animated GIF at 'path' using the default options.
+pub fn animate<P: AsRef<Path>>(
+    generator: &dyn Generator,
+    maze: &mut RectGrid,
+    path: P,
+) -> ImageResult<()> {
+    Animation::default().save(generator, maze, path)
+}
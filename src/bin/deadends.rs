@@ -1,41 +1,84 @@
-// External imports
+// Standard imports
 use std::collections::HashMap;
+use std::env;
+use std::thread;
 
 // Self imports
-use mazes::{AldousBroder, Backtracker, BinaryTree, HuntAndKill, Sidewinder, Wilsons};
-use mazes::{Generator, RectGrid};
+use mazes::{
+    AldousBroder, Backtracker, BinaryTree, Ellers, GrowingTree, Houston, HuntAndKill, Kruskal,
+    Prims, PrimsMode, RecursiveDivision, Sidewinder, WallWilsons, Wilsons,
+};
+use mazes::{Generator, Grid, RectGrid};
 
 fn main() {
     let tries = 25;
     let size = 25;
 
+    // Probability of removing each dead end when braiding. Can be set with the first argument.
+    let braid: f64 = env::args()
+        .nth(1)
+        .map(|arg| arg.parse().expect("braid probability must be a number"))
+        .unwrap_or(0.5);
+
     let algorithms: Vec<Box<dyn Generator>> = vec![
-        Box::new(BinaryTree {}),
-        Box::new(Sidewinder {}),
+        Box::new(BinaryTree::default()),
+        Box::new(Sidewinder::default()),
         Box::new(AldousBroder {}),
         Box::new(Wilsons {}),
+        Box::new(WallWilsons {}),
+        Box::new(Houston::default()),
         Box::new(HuntAndKill {}),
         Box::new(Backtracker {}),
+        Box::new(Kruskal {}),
+        Box::new(Ellers {}),
+        Box::new("newest:75,random:25".parse::<GrowingTree>().unwrap()),
+        Box::new(RecursiveDivision { room_size: 1 }),
+        Box::new(Prims {
+            mode: PrimsMode::Simplified,
+        }),
+        Box::new(Prims {
+            mode: PrimsMode::True,
+        }),
     ];
-    let mut averages: HashMap<String, f64> = HashMap::new();
+    // Run every algorithm on its own thread. Mazes are generated and counted on the worker threads.
+    // Average dead ends before and after braiding for every algorithm.
+    let averages: HashMap<String, (f64, f64)> = thread::scope(|scope| {
+        let handles: Vec<_> = algorithms
+            .iter()
+            .map(|alg| {
+                scope.spawn(move || {
+                    println!("Running: {:?}", alg);
 
-    for alg in algorithms.iter() {
-        println!("Running: {:?}", alg);
+                    let mut dead_end_counts: Vec<usize> = Vec::new();
+                    let mut braided_counts: Vec<usize> = Vec::new();
 
-        let mut dead_end_counts: Vec<usize> = Vec::new();
+                    for _ in 0..tries {
+                        let mut maze = RectGrid::new(size, size);
+                        alg.gen(&mut maze);
+                        dead_end_counts.push(dead_end_counter(&maze));
 
-        for _ in 0..tries {
-            let mut maze = RectGrid::new(25, 25);
-            alg.gen(&mut maze);
-            dead_end_counts.push(dead_end_counter(&maze));
-        }
+                        maze.braid(braid, &mut rand::thread_rng());
+                        braided_counts.push(dead_end_counter(&maze));
+                    }
 
-        let total_dead_ends = dead_end_counts.iter().fold(0, |acc, x| acc + x);
-        averages.insert(
-            format!("{:?}", alg),
-            total_dead_ends as f64 / dead_end_counts.len() as f64,
-        );
-    }
+                    let total_dead_ends = dead_end_counts.iter().sum::<usize>();
+                    let total_braided = braided_counts.iter().sum::<usize>();
+                    (
+                        format!("{:?}", alg),
+                        (
+                            total_dead_ends as f64 / tries as f64,
+                            total_braided as f64 / tries as f64,
+                        ),
+                    )
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
 
     println!("\n-------\n");
 
@@ -45,19 +88,20 @@ fn main() {
         size, size, total_cells
     );
 
-    for (alg, avg) in averages.iter() {
+    for (alg, (avg, braided)) in averages.iter() {
         let formatted = format!("{:.*}", 1, (*avg / total_cells as f64) * 100.0);
+        let braided_formatted = format!("{:.*}", 1, (*braided / total_cells as f64) * 100.0);
         println!(
-            "{}: {} out of {} ({:02}%)",
-            alg, avg, total_cells, formatted
+            "{}: {} out of {} ({:02}%), {} ({:02}%) after braiding with p = {}",
+            alg, avg, total_cells, formatted, braided, braided_formatted, braid
         );
     }
 }
 
-fn dead_end_counter(maze: &RectGrid) -> usize {
+fn dead_end_counter<G: Grid>(maze: &G) -> usize {
     let mut counter = 0;
-    for cell_rc in maze.iter_cell() {
-        if cell_rc.borrow().get_linked().len() == 1 {
+    for index in 0..maze.size() {
+        if maze.links(index).len() == 1 {
             counter += 1;
         }
     }
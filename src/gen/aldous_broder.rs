@@ -2,34 +2,33 @@
 use rand::prelude::*;
 
 // Self imports
-use crate::{Generator, RectGrid};
+use crate::{Generator, Grid, Step};
 
 #[derive(Debug)]
 pub struct AldousBroder {}
 
-impl Generator for AldousBroder {
-    fn gen(&self, maze: &mut RectGrid) {
-        let mut rng = thread_rng();
-
-        let mut cell_rc = maze.get_random_cell().unwrap();
-
-        let mut unvisited = maze.grid.len() - 1;
-        while unvisited > 0 {
-            // Pick a direction from list of directions neighbours lie in.
-            let nb_dirs = cell_rc.borrow().get_neighbours();
-            let nb_dir = nb_dirs.choose(&mut rng).unwrap();
-
-            // Get the neighbour in the chosen direction.
-            let nb_rc = cell_rc.borrow().get_neighbour(*nb_dir).unwrap();
-
-            // If the neighbour is not linked to any cells, link it to the current cell.
-            if nb_rc.borrow().get_linked().len() == 0 {
-                cell_rc.borrow_mut().link(*nb_dir);
-                unvisited -= 1;
+impl<G: Grid> Generator<G> for AldousBroder {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        for (start, cells) in maze.region_starts(rng) {
+            let mut index = start;
+            emit(Step::Visit(index));
+
+            let mut unvisited = cells - 1;
+            while unvisited > 0 {
+                // Pick a random neighbour.
+                let nb_index = *maze.neighbours(index).choose(rng).unwrap();
+
+                // If the neighbour is not linked to any cells, link it to the current cell.
+                if maze.links(nb_index).is_empty() {
+                    maze.link(index, nb_index);
+                    emit(Step::Link(index, nb_index));
+                    unvisited -= 1;
+                }
+
+                // Repeat with the neighbour as the current cell.
+                index = nb_index;
+                emit(Step::Visit(index));
             }
-
-            // Repeat with the neighbour as the current cell.
-            cell_rc = nb_rc;
         }
     }
 }
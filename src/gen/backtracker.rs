@@ -2,39 +2,36 @@
 use rand::prelude::*;
 
 // Self imports
-use crate::{Generator, HardCellLink, RectGrid};
+use crate::{Generator, Grid, Step};
 
 #[derive(Debug)]
 pub struct Backtracker {}
 
-impl Generator for Backtracker {
-    fn gen(&self, maze: &mut RectGrid) {
-        let mut rng = thread_rng();
-
-        let mut stack: Vec<HardCellLink> = Vec::new();
-        stack.push(maze.get_random_cell().unwrap());
-
-        while !stack.is_empty() {
-            let cell_rc = stack.last().unwrap();
-            let mut cell = cell_rc.borrow_mut();
-
-            // Pick a random unlinked neighbour
-            match cell.get_unlinked_neighbours().choose(&mut rng) {
-                // None only occurs when there are no unlinked neighbours. Move back to previous cell on stack.
-                None => {
-                    // Drop mutable reference to cell to allow item to be popped from stack.
-                    drop(cell);
-                    stack.pop();
-                }
-
-                // Link to chosen neighbour and add it to the top of the stack.
-                Some(&dir) => {
-                    let nb_rc = cell.get_neighbour(dir).unwrap();
-                    cell.link(dir);
-
-                    // Drop mutable reference to cell to allow nb_rc to be moved into stack.
-                    drop(cell);
-                    stack.push(nb_rc);
+impl<G: Grid> Generator<G> for Backtracker {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        let mut stack: Vec<usize> = Vec::new();
+
+        for (start, _) in maze.region_starts(rng) {
+            stack.push(start);
+            emit(Step::Visit(stack[0]));
+
+            while let Some(&index) = stack.last() {
+                // Pick a random unlinked neighbour
+                match maze.unlinked_neighbours(index).choose(rng) {
+                    // None only occurs when there are no unlinked neighbours. Move back to previous cell on stack.
+                    None => {
+                        stack.pop();
+                        emit(Step::Backtrack(index));
+                    }
+
+                    // Link to chosen neighbour and add it to the top of the stack.
+                    Some(&nb_index) => {
+                        maze.link(index, nb_index);
+                        emit(Step::Link(index, nb_index));
+
+                        stack.push(nb_index);
+                        emit(Step::Visit(nb_index));
+                    }
                 }
             }
         }
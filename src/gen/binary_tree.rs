@@ -1,37 +1,83 @@
 // External imports
-use rand::seq::SliceRandom;
+use rand::prelude::*;
 
 // Self imports
-use crate::{Direction::*, Generator, RectGrid};
+use crate::{Direction, Direction::*, Generator, RectGrid, Step};
 
+/// The pair of Directions a BinaryTree carves in. The maze gets an empty corridor along both of the matching edges.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Bias {
+    NE,
+    NW,
+    SE,
+    SW,
+}
+
+impl Bias {
+    /// Return the vertical and horizontal Directions of the Bias.
+    pub fn directions(self) -> (Direction, Direction) {
+        match self {
+            Bias::NE => (N, E),
+            Bias::NW => (N, W),
+            Bias::SE => (S, E),
+            Bias::SW => (S, W),
+        }
+    }
+}
+
+/// Link every Cell to its neighbour in one of the two Directions of 'bias'.
+/// 'weight' is the probability of carving horizontally rather than vertically when both neighbours exist. It is clamped between 0 and 1.
 #[derive(Debug)]
-pub struct BinaryTree {}
+pub struct BinaryTree {
+    pub bias: Bias,
+    pub weight: f64,
+}
+
+impl Default for BinaryTree {
+    fn default() -> Self {
+        BinaryTree {
+            bias: Bias::NE,
+            weight: 0.5,
+        }
+    }
+}
 
 impl Generator for BinaryTree {
-    fn gen(&self, maze: &mut RectGrid) {
-        for cell_rc in maze.iter_cell() {
-            // Get mutable access to the cell.
-            let mut cell = cell_rc.borrow_mut();
+    fn gen_with_events(
+        &self,
+        maze: &mut RectGrid,
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        if !maze.is_plain() {
+            return;
+        }
 
-            // Create vector to store possible neighbours to link to.
-            let mut possible = Vec::new();
+        let (vertical, horizontal) = self.bias.directions();
 
-            // If a northern neighbour exists, add it to the list of possible links.
-            if cell.neighbours.get(&N).unwrap().is_some() {
-                possible.push(N);
-            }
+        let weight = self.weight.clamp(0.0, 1.0);
 
-            // If a eastern neighbour exists, add it to the list of possible links.
-            if cell.neighbours.get(&E).unwrap().is_some() {
-                possible.push(E)
-            }
+        for index in 0..maze.size() {
+            emit(Step::Visit(index));
+
+            // Check which of the two neighbours exist.
+            let can_vertical = maze.get_index_relative(index, vertical).is_some();
+            let can_horizontal = maze.get_index_relative(index, horizontal).is_some();
 
-            // Choose a neighbour to link to.
-            let choice = possible.choose(&mut rand::thread_rng());
+            // Choose a neighbour to link to. Flip a weighted coin if both exist.
+            let choice = match (can_vertical, can_horizontal) {
+                (true, true) if rng.gen_bool(weight) => Some(horizontal),
+                (true, true) => Some(vertical),
+                (true, false) => Some(vertical),
+                (false, true) => Some(horizontal),
+                (false, false) => None,
+            };
 
             // If a neighbour was picked (possible there was no viable neighbour), link to it.
-            if let Some(&dir) = choice {
-                cell.link(dir)
+            if let Some(dir) = choice {
+                let nb_index = maze.get_index_relative(index, dir).unwrap();
+                maze.link(index, dir);
+                emit(Step::Link(index, nb_index));
             }
         }
     }
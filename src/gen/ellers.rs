@@ -0,0 +1,163 @@
+// External imports
+use rand::prelude::*;
+
+// Standard imports
+use std::collections::BTreeMap;
+
+// Self imports
+use crate::{Direction, Direction::*, Generator, RectGrid, Step};
+
+#[derive(Debug)]
+pub struct Ellers {}
+
+impl Generator for Ellers {
+    fn gen_with_events(
+        &self,
+        maze: &mut RectGrid,
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        if !maze.is_plain() {
+            return;
+        }
+
+        let mut state = RowState::new(maze.cols);
+
+        for row in 0..maze.rows {
+            let (east, south) = state.carve(row + 1 == maze.rows, rng);
+
+            for col in 0..maze.cols {
+                let index = row * maze.cols + col;
+                if east[col] {
+                    maze.link(index, E);
+                    emit(Step::Link(index, index + 1));
+                }
+                if south[col] {
+                    maze.link(index, S);
+                    emit(Step::Link(index, index + maze.cols));
+                }
+            }
+        }
+    }
+}
+
+impl Ellers {
+    /// Generate a maze one row at a time without ever holding more than a single row in memory.
+    /// 'emit' is called once per row, from north to south, with the row number and the Directions each Cell in that row is linked in.
+    /// The links are given in the same form as 'RectGrid::get_linked', so each row matches the Cells a RectGrid holds for that row.
+    /// The same random number generator state always produces the same rows as 'gen_with_rng' does for a RectGrid of the same size.
+    pub fn gen_rows<F>(&self, rows: usize, cols: usize, rng: &mut dyn RngCore, mut emit: F)
+    where
+        F: FnMut(usize, &[Vec<Direction>]),
+    {
+        let mut state = RowState::new(cols);
+
+        // Links to the south from the previous row. These become links to the north in the current row.
+        let mut north = vec![false; cols];
+        let mut links = vec![Vec::new(); cols];
+
+        for row in 0..rows {
+            let (east, south) = state.carve(row + 1 == rows, rng);
+
+            for (col, cell_links) in links.iter_mut().enumerate() {
+                cell_links.clear();
+
+                if north[col] {
+                    cell_links.push(N);
+                }
+                if east[col] {
+                    cell_links.push(E);
+                }
+                if south[col] {
+                    cell_links.push(S);
+                }
+                if col > 0 && east[col - 1] {
+                    cell_links.push(W);
+                }
+            }
+
+            emit(row, &links);
+            north = south;
+        }
+    }
+}
+
+/// Set membership of the Cells in the row currently being carved. Cells that haven't been assigned a set yet are None.
+struct RowState {
+    sets: Vec<Option<usize>>,
+    next_set: usize,
+}
+
+impl RowState {
+    /// Create the state for the first row. No Cell belongs to a set yet.
+    fn new(cols: usize) -> Self {
+        RowState {
+            sets: vec![None; cols],
+            next_set: 0,
+        }
+    }
+
+    /// Carve the current row and prepare the state for the next one.
+    /// Returns which Cells should be linked to the east and which should be linked to the south.
+    fn carve(&mut self, last: bool, rng: &mut dyn RngCore) -> (Vec<bool>, Vec<bool>) {
+        let cols = self.sets.len();
+        let mut east = vec![false; cols];
+        let mut south = vec![false; cols];
+
+        // Place every Cell that doesn't belong to a set into a new set of its own.
+        for set in self.sets.iter_mut().filter(|set| set.is_none()) {
+            *set = Some(self.next_set);
+            self.next_set += 1;
+        }
+
+        // Group the columns of the row by the set they belong to.
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (col, set) in self.sets.iter().enumerate() {
+            members.entry(set.unwrap()).or_default().push(col);
+        }
+
+        // Randomly link adjacent Cells in different sets. The last row must link all of them to connect the maze.
+        for (col, linked) in east.iter_mut().enumerate().take(cols.saturating_sub(1)) {
+            let (a, b) = (self.sets[col].unwrap(), self.sets[col + 1].unwrap());
+
+            if a != b && (last || rng.gen_bool(0.5)) {
+                *linked = true;
+
+                // Merge the smaller set into the larger one so each Cell is only moved a few times per row.
+                let (keep, gone) = if members[&a].len() >= members[&b].len() {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+
+                let moved = members.remove(&gone).unwrap();
+                for &c in &moved {
+                    self.sets[c] = Some(keep);
+                }
+                members.get_mut(&keep).unwrap().extend(moved);
+            }
+        }
+
+        if last {
+            return (east, south);
+        }
+
+        // Every set must link south at least once or it would be cut off from the rest of the maze.
+        for cols in members.values_mut() {
+            cols.shuffle(rng);
+            let count = rng.gen_range(1, cols.len() + 1);
+            for &col in &cols[..count] {
+                south[col] = true;
+            }
+        }
+
+        // Cells linked from the north keep their set. The rest get a new set in the next row.
+        for (set, &linked) in self.sets.iter_mut().zip(&south) {
+            if !linked {
+                *set = None;
+            }
+        }
+
+        (east, south)
+    }
+}
@@ -1,5 +1,161 @@
-use crate::RectGrid;
+// External imports
+use rand::{thread_rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-pub trait Generator: std::fmt::Debug {
-    fn gen(&self, grid: &mut RectGrid);
+// Self imports
+use crate::{Grid, RectGrid, Step, Steps};
+
+/// A maze generation algorithm for grids of type 'G'. Generators are Send and Sync so they can be shared with, and run on, worker threads.
+/// Algorithms that only need the neighbours of each Cell implement Generator for every Grid. Algorithms that rely on rows, columns
+/// or the walls between them (BinaryTree, Sidewinder, Ellers, RecursiveDivision and WallWilsons) only work on a RectGrid,
+/// and leave it untouched if it is masked or wrapped. See 'RectGrid::is_plain'.
+pub trait Generator<G: Grid = RectGrid>: std::fmt::Debug + Send + Sync {
+    /// Generate a maze in the grid, taking every random decision from 'rng'. Every Step is passed to 'emit' as it is taken.
+    fn gen_with_events(&self, grid: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step));
+
+    /// Generate a maze in the grid, taking every random decision from 'rng'.
+    fn gen_with_rng(&self, grid: &mut G, rng: &mut dyn RngCore) {
+        self.gen_with_events(grid, rng, &mut |_| {});
+    }
+
+    /// Generate a maze in the grid using the thread local random number generator.
+    fn gen(&self, grid: &mut G) {
+        self.gen_with_rng(grid, &mut thread_rng());
+    }
+
+    /// Generate a maze in the grid from a seed. The same seed, Generator and grid size always produce the same maze.
+    fn gen_seeded(&self, grid: &mut G, seed: u64) {
+        self.gen_with_rng(grid, &mut ChaCha8Rng::seed_from_u64(seed));
+    }
+
+    /// Generate a maze on a copy of the grid using the thread local random number generator, recording every Step.
+    /// See 'record_steps_with_rng'.
+    fn record_steps<'a>(&self, grid: &'a mut G) -> Steps<'a, G> {
+        self.record_steps_with_rng(grid, &mut thread_rng())
+    }
+
+    /// Generate a maze on a copy of the grid, taking every random decision from 'rng', and record every Step.
+    /// Returns an Iterator that replays the recording onto the grid one Step at a time. The whole maze is generated before
+    /// this returns and every Step is held in memory, so use 'gen_with_events' to watch Steps as they happen on large grids.
+    fn record_steps_with_rng<'a>(&self, grid: &'a mut G, rng: &mut dyn RngCore) -> Steps<'a, G> {
+        let mut steps = Vec::new();
+        self.gen_with_events(&mut grid.clone(), rng, &mut |step| steps.push(step));
+        Steps::new(grid, steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn generators() -> Vec<Box<dyn Generator>> {
+        vec![
+            Box::new(AldousBroder {}),
+            Box::new(Backtracker {}),
+            Box::new(BinaryTree::default()),
+            Box::new(Ellers {}),
+            Box::new(GrowingTree::new(Selector::Random)),
+            Box::new(Houston::default()),
+            Box::new(HuntAndKill {}),
+            Box::new(Kruskal {}),
+            Box::new(Prims {
+                mode: PrimsMode::Simplified,
+            }),
+            Box::new(Prims {
+                mode: PrimsMode::True,
+            }),
+            Box::new(RecursiveDivision { room_size: 1 }),
+            Box::new(Sidewinder::default()),
+            Box::new(WallWilsons {}),
+            Box::new(Wilsons {}),
+        ]
+    }
+
+    /// Assert that every region of the maze is a spanning tree: every Cell in it can be reached and there are no loops.
+    fn assert_spanning_tree(maze: &RectGrid, name: &str) {
+        let regions = maze.region_starts(&mut rand::thread_rng());
+        for &(start, cells) in &regions {
+            let reached = maze.distances(start).iter().flatten().count();
+            assert_eq!(reached, cells, "{} left Cells unreachable", name);
+        }
+
+        let cells: usize = regions.iter().map(|&(_, cells)| cells).sum();
+        let edges = (0..maze.size())
+            .map(|index| Grid::links(maze, index).len())
+            .sum::<usize>()
+            / 2;
+        assert_eq!(edges, cells - regions.len(), "{} made loops", name);
+    }
+
+    #[test]
+    fn same_seed_gives_same_maze() {
+        for generator in generators() {
+            let mut a = RectGrid::new(12, 15);
+            let mut b = RectGrid::new(12, 15);
+            generator.gen_seeded(&mut a, 42);
+            generator.gen_seeded(&mut b, 42);
+            assert_eq!(a, b, "{:?} isn't reproducible", generator);
+        }
+    }
+
+    #[test]
+    fn generators_carve_spanning_trees() {
+        for generator in generators() {
+            for seed in 0..5 {
+                let mut maze = RectGrid::new(12, 15);
+                generator.gen_seeded(&mut maze, seed);
+                assert_spanning_tree(&maze, &format!("{:?}", generator));
+            }
+        }
+    }
+
+    #[test]
+    fn row_generators_leave_masked_and_wrapped_grids_untouched() {
+        let generators: Vec<Box<dyn Generator>> = vec![
+            Box::new(BinaryTree::default()),
+            Box::new(Ellers {}),
+            Box::new(RecursiveDivision { room_size: 1 }),
+            Box::new(Sidewinder::default()),
+            Box::new(WallWilsons {}),
+        ];
+
+        for generator in generators {
+            for maze in &[
+                RectGrid::with_mask(Mask::from_ascii("..X..\n..X..\n")),
+                RectGrid::new(4, 5).wrapped(Wrap::Torus),
+            ] {
+                let mut carved = maze.clone();
+                generator.gen_seeded(&mut carved, 0);
+                assert_eq!(
+                    &carved, maze,
+                    "{:?} changed a grid it can't carve",
+                    generator
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generators_carve_every_region_of_a_split_mask() {
+        let generators: Vec<Box<dyn Generator>> = vec![
+            Box::new(AldousBroder {}),
+            Box::new(Backtracker {}),
+            Box::new(GrowingTree::new(Selector::Newest)),
+            Box::new(Houston::default()),
+            Box::new(HuntAndKill {}),
+            Box::new(Kruskal {}),
+            Box::new(Prims {
+                mode: PrimsMode::True,
+            }),
+            Box::new(Wilsons {}),
+        ];
+
+        for generator in generators {
+            for mask in &["..X..\n..X..\n", ".X.\nXXX\n"] {
+                let mut maze = RectGrid::with_mask(Mask::from_ascii(mask));
+                generator.gen_seeded(&mut maze, 0);
+                assert_spanning_tree(&maze, &format!("{:?}", generator));
+            }
+        }
+    }
 }
@@ -0,0 +1,158 @@
+// External imports
+use rand::prelude::*;
+
+// Standard imports
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+// Self imports
+use crate::{Generator, Grid, Step};
+
+/// Strategy used to pick the next Cell to grow from out of the active list.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// Most recently added Cell. Behaves like the Recursive Backtracker.
+    Newest,
+
+    /// Least recently added Cell.
+    Oldest,
+
+    /// Cell in the middle of the active list. Removing Cells from the middle of the list takes time proportional to its length,
+    /// so this is much slower than the other Selectors on large mazes.
+    Middle,
+
+    /// Any Cell at random. Behaves like simplified Prim's.
+    Random,
+}
+
+impl Selector {
+    /// Return the position in an active list of given length that this Selector picks.
+    fn select(self, len: usize, rng: &mut dyn RngCore) -> usize {
+        match self {
+            Selector::Newest => len - 1,
+            Selector::Oldest => 0,
+            Selector::Middle => len / 2,
+            Selector::Random => rng.gen_range(0, len),
+        }
+    }
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "newest" => Ok(Selector::Newest),
+            "oldest" => Ok(Selector::Oldest),
+            "middle" => Ok(Selector::Middle),
+            "random" => Ok(Selector::Random),
+            other => Err(format!("unknown selector '{}'", other)),
+        }
+    }
+}
+
+/// Grow a maze from a list of active Cells. The Selectors and their weights decide which active Cell is grown each step.
+/// A single Selector reproduces a fixed algorithm while a mix, e.g. 75% Newest and 25% Random, blends their textures.
+#[derive(Debug, Clone)]
+pub struct GrowingTree {
+    pub selectors: Vec<(Selector, u32)>,
+}
+
+impl GrowingTree {
+    /// Create a GrowingTree that always uses the given Selector.
+    pub fn new(selector: Selector) -> Self {
+        GrowingTree {
+            selectors: vec![(selector, 1)],
+        }
+    }
+
+    /// Choose a Selector from the weighted list.
+    fn pick(&self, rng: &mut dyn RngCore) -> Selector {
+        self.selectors
+            .choose_weighted(rng, |(_, weight)| *weight)
+            .expect("growing tree needs at least one selector with a non-zero weight")
+            .0
+    }
+}
+
+/// Parse a comma separated list of Selectors with optional weights, e.g. "newest" or "newest:75,random:25".
+impl FromStr for GrowingTree {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut selectors = Vec::new();
+
+        for part in s.split(',') {
+            let mut split = part.splitn(2, ':');
+            let selector = split.next().unwrap().parse()?;
+            let weight = match split.next() {
+                Some(weight) => weight
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid weight '{}'", weight))?,
+                None => 1,
+            };
+            selectors.push((selector, weight));
+        }
+
+        if selectors.iter().all(|&(_, weight)| weight == 0) {
+            return Err("at least one selector needs a non-zero weight".to_string());
+        }
+
+        Ok(GrowingTree { selectors })
+    }
+}
+
+impl<G: Grid> Generator<G> for GrowingTree {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        let mut visited = vec![false; maze.size()];
+        let mut active = VecDeque::new();
+
+        // The order of the active list only matters if a Selector other than Random picks from it.
+        let ordered = self
+            .selectors
+            .iter()
+            .any(|&(selector, _)| selector != Selector::Random);
+
+        for (start, _) in maze.region_starts(rng) {
+            // Start from the random start of the region.
+            let index = start;
+            visited[index] = true;
+            active.push_back(index);
+            emit(Step::Visit(index));
+
+            while !active.is_empty() {
+                let position = self.pick(rng).select(active.len(), rng);
+                let index = active[position];
+
+                // Find neighbours that haven't been visited yet.
+                let nbs: Vec<_> = maze
+                    .neighbours(index)
+                    .into_iter()
+                    .filter(|&nb| !visited[nb])
+                    .collect();
+
+                match nbs.choose(rng) {
+                    // Link to the neighbour and add it to the end of the active list.
+                    Some(&nb_index) => {
+                        maze.link(index, nb_index);
+                        emit(Step::Link(index, nb_index));
+
+                        visited[nb_index] = true;
+                        active.push_back(nb_index);
+                        emit(Step::Visit(nb_index));
+                    }
+
+                    // Cell has no unvisited neighbours. Remove it, keeping the order of the active list if it matters.
+                    None => {
+                        match ordered {
+                            true => active.remove(position),
+                            false => active.swap_remove_back(position),
+                        };
+                        emit(Step::Backtrack(index));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// External imports
+use rand::prelude::*;
+
+// Self imports
+use crate::gen::wilsons::loop_erased_walks;
+use crate::{Generator, Grid, Step};
+
+/// Hybrid of Aldous-Broder and Wilson's. Aldous-Broder is fast while most Cells are unvisited and Wilson's is fast once most are visited.
+/// Runs the Aldous-Broder random walk until 'threshold' (a fraction between 0 and 1) of the Cells have been visited, then finishes with Wilson's.
+/// Both halves are unbiased so the result is still a uniform spanning tree.
+#[derive(Debug)]
+pub struct Houston {
+    pub threshold: f64,
+}
+
+impl Default for Houston {
+    fn default() -> Self {
+        Houston {
+            threshold: 1.0 / 3.0,
+        }
+    }
+}
+
+impl<G: Grid> Generator<G> for Houston {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        // Masked cells count as visited.
+        let mut unvisited: Vec<bool> = (0..maze.size()).map(|i| !maze.is_masked(i)).collect();
+        let masked = unvisited.iter().filter(|&&u| !u).count();
+
+        let mut visited = masked;
+
+        for (start, cells) in maze.region_starts(rng) {
+            // Start the walk from the random start of the region and set it as visited.
+            let mut index = start;
+            unvisited[index] = false;
+            let mut region_visited = 1;
+            visited += 1;
+            emit(Step::Visit(index));
+
+            // Number of cells the random walk should visit before switching to Wilson's.
+            // Thresholds outside 0 to 1 are clamped, as the walk can never visit more cells than the region has.
+            let target = (cells as f64 * self.threshold.clamp(0.0, 1.0)).ceil() as usize;
+
+            while region_visited < target {
+                // Pick a random neighbour.
+                let nb_index = *maze.neighbours(index).choose(rng).unwrap();
+
+                // If the neighbour hasn't been visited, link it to the current cell.
+                if unvisited[nb_index] {
+                    maze.link(index, nb_index);
+                    emit(Step::Link(index, nb_index));
+                    unvisited[nb_index] = false;
+                    region_visited += 1;
+                    visited += 1;
+                }
+
+                // Repeat with the neighbour as the current cell.
+                index = nb_index;
+                emit(Step::Visit(index));
+            }
+        }
+
+        loop_erased_walks(maze, &mut unvisited, visited, rng, emit);
+    }
+}
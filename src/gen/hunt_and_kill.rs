@@ -2,69 +2,50 @@
 use rand::prelude::*;
 
 // Self imports
-use crate::{Generator, RectGrid};
+use crate::{Generator, Grid, Step};
 
 #[derive(Debug)]
 pub struct HuntAndKill {}
 
-impl Generator for HuntAndKill {
-    fn gen(&self, maze: &mut RectGrid) {
-        let mut rng = thread_rng();
+impl<G: Grid> Generator<G> for HuntAndKill {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        for (start, _) in maze.region_starts(rng) {
+            let mut current = Some(start);
 
-        let mut cell_rc = maze.get_random_cell();
+            while let Some(index) = current {
+                emit(Step::Visit(index));
 
-        while cell_rc.is_some() {
-            let mut cell = cell_rc.as_ref().unwrap().borrow_mut();
+                // Get a list of the current cells unvisited neighbours.
+                let nbs = maze.unlinked_neighbours(index);
 
-            // Get a list of the current cells unvisited neighbours.
-            let nb_dirs = cell.get_unlinked_neighbours();
+                // Choose a random neighbour.
+                match nbs.choose(rng) {
+                    // If a neighbour was chosen (i.e. an unvisited neighbour existed), link to it.
+                    Some(&nb_index) => {
+                        maze.link(index, nb_index);
+                        emit(Step::Link(index, nb_index));
 
-            // Choose a random neighbour.
-            match nb_dirs.choose(&mut rng) {
-                // If a neighbour was chosen (i.e. an unvisited neighbour existed), link to it.
-                Some(&nb_dir) => {
-                    let nb_rc = cell.get_neighbour(nb_dir);
-
-                    /*
-                    println!(
-                        "Neighbours: {:?} --> Linked: {:?} --> Unlinked: {:?} --> Choice: {:?}",
-                        cell.get_neighbours(),
-                        cell.get_linked(),
-                        cell.get_unlinked(),
-                        nb_dir
-                    );
-                     */
-
-                    cell.link(nb_dir);
-
-                    // Drop the mutable reference to the cell to allow 'cell_rc' to be reassigned.
-                    drop(cell);
-                    cell_rc = nb_rc;
-
-                    // println!("{}", maze);
-                }
-
-                None => {
-                    // Drop mutable reference to the cell to allow 'cell_rc' to be reassigned.
-                    drop(cell);
-                    cell_rc = None;
+                        current = Some(nb_index);
+                    }
 
-                    for c_rc in maze.iter_cell() {
-                        let mut c = c_rc.borrow_mut();
+                    None => {
+                        // The walk is over. Hunt for the first unvisited cell that borders a visited cell.
+                        emit(Step::Backtrack(index));
+                        current = None;
 
-                        // If the current cell is unvisited check if it has any visited neighborus.
-                        if c.get_linked().is_empty() {
-                            let visited_neighbours = c.get_linked_neighbours();
+                        for c in 0..maze.size() {
+                            // If the current cell is unvisited check if it has any visited neighbours.
+                            if maze.links(c).is_empty() {
+                                let visited_neighbours = maze.linked_neighbours(c);
 
-                            // If there are visited neighbours, link to one of them and set current cell as next cell.
-                            if !visited_neighbours.is_empty() {
-                                let nb_dir = visited_neighbours.choose(&mut rng).unwrap();
-                                c.link(*nb_dir);
+                                // If there are visited neighbours, link to one of them and set current cell as next cell.
+                                if let Some(&nb_index) = visited_neighbours.choose(rng) {
+                                    maze.link(c, nb_index);
+                                    emit(Step::Link(c, nb_index));
 
-                                // Drop mutable reference to the cell to allow 'c_rc' to be reassigned.
-                                drop(c);
-                                cell_rc = Some(c_rc);
-                                break;
+                                    current = Some(c);
+                                    break;
+                                }
                             }
                         }
                     }
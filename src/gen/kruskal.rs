@@ -0,0 +1,78 @@
+// External imports
+use rand::prelude::*;
+
+// Self imports
+use crate::{Generator, Grid, Step};
+
+#[derive(Debug)]
+pub struct Kruskal {}
+
+impl<G: Grid> Generator<G> for Kruskal {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        // Every cell starts in its own set.
+        let mut sets = DisjointSet::new(maze.size());
+
+        // Build a list of every interior wall. Each wall is stored once, by the cell with the lower index.
+        let mut walls: Vec<(usize, usize)> = Vec::new();
+        for index in 0..maze.size() {
+            for nb_index in maze.neighbours(index) {
+                if nb_index > index {
+                    walls.push((index, nb_index));
+                }
+            }
+        }
+
+        walls.shuffle(rng);
+
+        for (index, nb_index) in walls {
+            // Only carve the wall if the cells on either side aren't already connected.
+            if sets.union(index, nb_index) {
+                maze.link(index, nb_index);
+                emit(Step::Link(index, nb_index));
+            }
+        }
+    }
+}
+
+/// Union-find over the indices of the Cells of a Grid. Uses path halving and union by size.
+pub(crate) struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// Create a DisjointSet where every index is in a set of its own.
+    pub(crate) fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+        }
+    }
+
+    /// Return the index representing the set the given index belongs to.
+    pub(crate) fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// Merge the sets containing 'a' and 'b'. Return false if they were already in the same set.
+    pub(crate) fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // Attach the smaller tree to the root of the larger one.
+        let (big, small) = if self.sizes[a] >= self.sizes[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[small] = big;
+        self.sizes[big] += self.sizes[small];
+        true
+    }
+}
@@ -1,7 +1,16 @@
 pub mod aldous_broder;
 pub mod backtracker;
 pub mod binary_tree;
+pub mod ellers;
 pub mod generator;
+pub mod growing_tree;
+pub mod houston;
 pub mod hunt_and_kill;
+pub mod kruskal;
+pub mod prims;
+pub mod recursive_division;
 pub mod sidewinder;
+pub mod step;
+pub mod wall_wilsons;
+pub mod weave_kruskal;
 pub mod wilsons;
@@ -0,0 +1,80 @@
+// External imports
+use rand::prelude::*;
+
+// Standard imports
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Self imports
+use crate::{Generator, Grid, GrowingTree, Selector, Step};
+
+/// The variant of Prim's algorithm to run.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PrimsMode {
+    /// Grow the maze from a random cell in the active set. The same as a GrowingTree with the Random Selector.
+    Simplified,
+
+    /// Give every cell a random weight and always grow from the cheapest cell in the active set.
+    True,
+}
+
+#[derive(Debug)]
+pub struct Prims {
+    pub mode: PrimsMode,
+}
+
+impl<G: Grid> Generator<G> for Prims {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        match self.mode {
+            PrimsMode::Simplified => {
+                GrowingTree::new(Selector::Random).gen_with_events(maze, rng, emit)
+            }
+            PrimsMode::True => weighted(maze, rng, emit),
+        }
+    }
+}
+
+/// Assign every cell a random cost. Always extend the cheapest active cell into its cheapest unvisited neighbour.
+fn weighted<G: Grid>(maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+    // Costs cover the whole range of u32. Ties would be broken towards lower indices and bias the maze, so they need to be rare.
+    let costs: Vec<u32> = (0..maze.size()).map(|_| rng.gen()).collect();
+    let mut visited = vec![false; maze.size()];
+
+    // Min-heap of (cost, index) for every active cell.
+    let mut active = BinaryHeap::new();
+
+    for (start, _) in maze.region_starts(rng) {
+        // Start from the random start of the region.
+        let index = start;
+        visited[index] = true;
+        active.push(Reverse((costs[index], index)));
+        emit(Step::Visit(index));
+
+        while let Some(&Reverse((_, index))) = active.peek() {
+            // Find the cheapest neighbour that hasn't been visited yet.
+            let nb_index = maze
+                .neighbours(index)
+                .into_iter()
+                .filter(|&nb| !visited[nb])
+                .min_by_key(|&nb| costs[nb]);
+
+            match nb_index {
+                // Link to the neighbour and add it to the active set.
+                Some(nb_index) => {
+                    maze.link(index, nb_index);
+                    emit(Step::Link(index, nb_index));
+
+                    visited[nb_index] = true;
+                    active.push(Reverse((costs[nb_index], nb_index)));
+                    emit(Step::Visit(nb_index));
+                }
+
+                // Cell has no unvisited neighbours. Remove it from the active set.
+                None => {
+                    active.pop();
+                    emit(Step::Backtrack(index));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// External imports
+use rand::prelude::*;
+
+// Self imports
+use crate::{Direction::*, Generator, RectGrid, Step};
+
+/// Start from an empty room and repeatedly split it in two with a wall containing a single gap.
+/// Regions no larger than 'room_size' x 'room_size' aren't divided any further and are left as open rooms.
+/// A 'room_size' of 1 divides everything and creates a perfect maze.
+#[derive(Debug)]
+pub struct RecursiveDivision {
+    pub room_size: usize,
+}
+
+impl Generator for RecursiveDivision {
+    fn gen_with_events(
+        &self,
+        maze: &mut RectGrid,
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        if !maze.is_plain() {
+            return;
+        }
+
+        // Remove every interior wall before adding new ones.
+        for (a, b) in maze.link_all() {
+            emit(Step::Link(a, b));
+        }
+
+        let (rows, cols) = (maze.rows, maze.cols);
+        self.divide(maze, (0, 0), (rows, cols), rng, emit);
+    }
+}
+
+impl RecursiveDivision {
+    /// Divide the region with the given top left corner (row, col) and size (height, width). Recurses into both halves.
+    fn divide(
+        &self,
+        maze: &mut RectGrid,
+        (row, col): (usize, usize),
+        (height, width): (usize, usize),
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        // Regions a single Cell wide can't be divided. Small enough regions are left as rooms.
+        if height <= 1 || width <= 1 || (height <= self.room_size && width <= self.room_size) {
+            return;
+        }
+
+        // Divide across the longest side of the region. Pick at random for square regions.
+        let horizontal = match height.cmp(&width) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => rng.gen_bool(0.5),
+        };
+
+        if horizontal {
+            // Add a wall south of row 'row + offset' leaving a gap at a single column.
+            let offset = rng.gen_range(0, height - 1);
+            let gap = rng.gen_range(0, width);
+
+            for c in (col..col + width).filter(|&c| c != col + gap) {
+                let index = (row + offset) * maze.cols + c;
+                maze.unlink(index, S);
+                emit(Step::Unlink(index, index + maze.cols));
+            }
+
+            self.divide(maze, (row, col), (offset + 1, width), rng, emit);
+            let rest = (height - offset - 1, width);
+            self.divide(maze, (row + offset + 1, col), rest, rng, emit);
+        } else {
+            // Add a wall east of column 'col + offset' leaving a gap at a single row.
+            let offset = rng.gen_range(0, width - 1);
+            let gap = rng.gen_range(0, height);
+
+            for r in (row..row + height).filter(|&r| r != row + gap) {
+                let index = r * maze.cols + col + offset;
+                maze.unlink(index, E);
+                emit(Step::Unlink(index, index + 1));
+            }
+
+            self.divide(maze, (row, col), (height, offset + 1), rng, emit);
+            let rest = (height, width - offset - 1);
+            self.divide(maze, (row, col + offset + 1), rest, rng, emit);
+        }
+    }
+}
@@ -1,50 +1,85 @@
 // External imports
 use rand::prelude::*;
 
-// Standard imports
-use std::rc::Rc;
-
 // Self imports
-use crate::{Direction::*, Generator, HardCellLink, RectGrid};
+use crate::{Direction, Direction::*, Generator, RectGrid, Step};
 
+/// Carve runs of Cells parallel to 'edge', closing each run by linking one of its Cells towards 'edge'.
+/// The maze gets an empty corridor along 'edge'. 'weight' is the probability of extending the current run rather than closing it. It is clamped between 0 and 1.
 #[derive(Debug)]
-pub struct Sidewinder {}
+pub struct Sidewinder {
+    pub edge: Direction,
+    pub weight: f64,
+}
+
+impl Default for Sidewinder {
+    fn default() -> Self {
+        Sidewinder {
+            edge: N,
+            weight: 0.5,
+        }
+    }
+}
 
 impl Generator for Sidewinder {
-    fn gen(&self, maze: &mut RectGrid) {
-        let mut rng = rand::thread_rng();
+    fn gen_with_events(
+        &self,
+        maze: &mut RectGrid,
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        if !maze.is_plain() {
+            return;
+        }
+
+        let weight = self.weight.clamp(0.0, 1.0);
+
+        // Runs go along rows for the North and South edges and along columns for the East and West edges.
+        let run_dir = match self.edge {
+            N | S => E,
+            E | W => S,
+        };
 
-        // Iterate over every row
-        for row in maze.iter_row() {
-            // Vec to store all cells that make up the current "run".
-            let mut run: Vec<HardCellLink> = Vec::new();
+        // Index of the first cell of every row (or column).
+        let starts: Vec<usize> = match run_dir {
+            E => (0..maze.rows).map(|row| row * maze.cols).collect(),
+            _ => (0..maze.cols).collect(),
+        };
 
-            // Iterate over every cell in the row
-            for cell_ref in row {
-                // Clone 'cell_ref' to get owned RC to current cell.
-                let cell_rc = Rc::clone(cell_ref);
+        for start in starts {
+            // Vec to store the indices of all cells that make up the current "run".
+            let mut run: Vec<usize> = Vec::new();
+            let mut index = Some(start);
 
-                // Check if cell is at the north or east boundary.
-                let at_east_boundary = !cell_rc.borrow_mut().neighbour_exists(E);
-                let at_north_boundary = !cell_rc.borrow_mut().neighbour_exists(N);
+            // Iterate over every cell in the row (or column).
+            while let Some(i) = index {
+                emit(Step::Visit(i));
 
-                // The run should end at the east boundary. Should also have a 50% chance to end if not at the north boundary.
-                let should_end_run =
-                    at_east_boundary || (!at_north_boundary && rng.gen_range(0, 2) == 1);
+                // Check if cell is at the end of the run or at the open edge.
+                let at_run_end = maze.get_index_relative(i, run_dir).is_none();
+                let at_edge = maze.get_index_relative(i, self.edge).is_none();
 
-                // Add Cell to the 'run vector to make linking north logic simpler.
-                run.push(Rc::clone(&cell_rc));
+                // The run must end at the end of the row. Otherwise it ends at random, unless it's along the open edge.
+                let should_end_run = at_run_end || (!at_edge && !rng.gen_bool(weight));
 
-                // If run should end, link north if not at the north boundary. Clear the 'run' vec. Link east otherwise.
+                // Add Cell to the 'run vector to make linking towards the edge simpler.
+                run.push(i);
+
+                // If run should end, link towards the edge if possible and clear the 'run' vec. Extend the run otherwise.
                 if should_end_run {
-                    if !at_north_boundary {
-                        let choice = run.choose(&mut rng).unwrap();
-                        choice.borrow_mut().link(N);
+                    if !at_edge {
+                        let choice = *run.choose(rng).unwrap();
+                        let nb_index = maze.get_index_relative(choice, self.edge).unwrap();
+                        maze.link(choice, self.edge);
+                        emit(Step::Link(choice, nb_index));
                     }
-                    run = Vec::new();
+                    run.clear();
                 } else {
-                    cell_rc.borrow_mut().link(E);
+                    maze.link(i, run_dir);
+                    emit(Step::Link(i, maze.get_index_relative(i, run_dir).unwrap()));
                 }
+
+                index = maze.get_index_relative(i, run_dir);
             }
         }
     }
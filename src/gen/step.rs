@@ -0,0 +1,66 @@
+// Self imports
+use crate::{Grid, RectGrid};
+
+/// A single step taken by a Generator while carving a maze.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// The Cells at the two indices were linked.
+    Link(usize, usize),
+
+    /// The Cells at the two indices were unlinked.
+    Unlink(usize, usize),
+
+    /// The Cell at the index became the current Cell (e.g. the head of a walk or the Cell being grown from).
+    Visit(usize),
+
+    /// The Generator moved away from the Cell at the index for good, e.g. popped it off a stack or erased it from a loop.
+    Backtrack(usize),
+}
+
+impl Step {
+    /// Apply the Step to a grid. Only Link and Unlink change the grid.
+    pub fn apply<G: Grid>(&self, grid: &mut G) {
+        match *self {
+            Step::Link(a, b) => grid.link(a, b),
+            Step::Unlink(a, b) => grid.unlink(a, b),
+            Step::Visit(_) | Step::Backtrack(_) => {}
+        }
+    }
+}
+
+/// Iterator replaying the recorded Steps taken to generate a maze. Each Step is applied to the grid as it is returned,
+/// so the grid can be inspected (or drawn) between Steps with 'Steps::grid'.
+pub struct Steps<'a, G: Grid = RectGrid> {
+    grid: &'a mut G,
+    steps: std::vec::IntoIter<Step>,
+}
+
+impl<'a, G: Grid> Steps<'a, G> {
+    pub fn new(grid: &'a mut G, steps: Vec<Step>) -> Self {
+        Steps {
+            grid,
+            steps: steps.into_iter(),
+        }
+    }
+
+    /// Return the grid in its state after the most recent Step.
+    pub fn grid(&self) -> &G {
+        self.grid
+    }
+}
+
+impl<'a, G: Grid> Iterator for Steps<'a, G> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let step = self.steps.next()?;
+        step.apply(self.grid);
+        Some(step)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+}
+
+impl<'a, G: Grid> ExactSizeIterator for Steps<'a, G> {}
@@ -0,0 +1,97 @@
+// External imports
+use rand::prelude::*;
+
+// Self imports
+use crate::{Direction, Direction::*, Generator, RectGrid, Step};
+
+/// Wilson's algorithm run on the walls of the maze rather than its Cells.
+/// Loop-erased random walks move between the corners where walls meet. Every corner on the outer boundary starts as part of the tree,
+/// so each walk only has to reach the boundary or an existing wall instead of a single visited Cell. The walls of a perfect maze
+/// always form a tree attached to the boundary, so the result is still a uniform spanning tree.
+#[derive(Debug)]
+pub struct WallWilsons {}
+
+impl Generator for WallWilsons {
+    fn gen_with_events(
+        &self,
+        maze: &mut RectGrid,
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        if !maze.is_plain() {
+            return;
+        }
+
+        // Start from an empty room. Walls are added by unlinking Cells.
+        for (a, b) in maze.link_all() {
+            emit(Step::Link(a, b));
+        }
+
+        // Corners form a grid one larger than the maze in each dimension.
+        let corner_cols = maze.cols + 1;
+        let corners = (maze.rows + 1) * corner_cols;
+
+        // Corners on the boundary are already part of the tree.
+        let mut in_tree: Vec<bool> = (0..corners)
+            .map(|i| {
+                let (row, col) = (i / corner_cols, i % corner_cols);
+                row == 0 || col == 0 || row == maze.rows || col == maze.cols
+            })
+            .collect();
+
+        // The direction the walk last left each corner in.
+        let mut exits: Vec<Option<Direction>> = vec![None; corners];
+
+        // Start a walk from every interior corner, in a random order.
+        let mut starts: Vec<usize> = (0..corners).filter(|&i| !in_tree[i]).collect();
+        starts.shuffle(rng);
+
+        for start in starts {
+            // Walk until the tree is reached. Interior corners always have a neighbour in every direction.
+            let mut corner = start;
+            while !in_tree[corner] {
+                let dir = *[N, E, S, W].choose(rng).unwrap();
+                exits[corner] = Some(dir);
+                corner = corner_relative(corner, corner_cols, dir);
+            }
+
+            // Retrace the loop-erased walk, adding a wall for every step.
+            let mut corner = start;
+            while !in_tree[corner] {
+                let dir = exits[corner].unwrap();
+                let (index, wall) =
+                    wall_between(maze, corner / corner_cols, corner % corner_cols, dir);
+                let nb_index = maze.get_index_relative(index, wall).unwrap();
+                maze.unlink(index, wall);
+                emit(Step::Unlink(index, nb_index));
+
+                in_tree[corner] = true;
+                corner = corner_relative(corner, corner_cols, dir);
+            }
+        }
+    }
+}
+
+/// Return the index of the corner next to the given interior corner in the given Direction.
+fn corner_relative(corner: usize, corner_cols: usize, d: Direction) -> usize {
+    match d {
+        N => corner - corner_cols,
+        S => corner + corner_cols,
+        E => corner + 1,
+        W => corner - 1,
+    }
+}
+
+/// Return the Cell index and Direction of the wall running from the corner at (row, col) in the given Direction.
+/// The corner at (row, col) is the top left corner of the Cell at (row, col).
+fn wall_between(maze: &RectGrid, row: usize, col: usize, d: Direction) -> (usize, Direction) {
+    // Horizontal walls are the North wall of the Cell below them. Vertical walls are the West wall of the Cell to their right.
+    let (row, col, wall) = match d {
+        E => (row, col, N),
+        W => (row, col - 1, N),
+        S => (row, col, W),
+        N => (row - 1, col, W),
+    };
+
+    (row * maze.cols + col, wall)
+}
@@ -0,0 +1,99 @@
+// External imports
+use rand::prelude::*;
+
+// Self imports
+use crate::gen::kruskal::DisjointSet;
+use crate::{Direction::*, Generator, Grid, RectGrid, Step};
+
+/// Kruskal's algorithm with crossings woven into the maze before it starts.
+/// Random Cells are first turned into crossings, with one passage running straight through the Cell and another tunnelling underneath it
+/// at right angles. The rest of the maze is then carved by Kruskal's as normal, which joins everything up around the crossings.
+/// 'crossings' is the number of attempts at placing a crossing for every Cell in the maze. Attempts that don't fit are skipped.
+/// Requires a grid created with 'RectGrid::woven'.
+#[derive(Debug)]
+pub struct WeaveKruskal {
+    pub crossings: f64,
+}
+
+impl Default for WeaveKruskal {
+    fn default() -> Self {
+        WeaveKruskal { crossings: 1.0 }
+    }
+}
+
+impl Generator for WeaveKruskal {
+    fn gen_with_events(
+        &self,
+        maze: &mut RectGrid,
+        rng: &mut dyn RngCore,
+        emit: &mut dyn FnMut(Step),
+    ) {
+        assert!(maze.is_woven(), "WeaveKruskal needs a woven grid");
+
+        // Every cell starts in its own set.
+        let mut sets = DisjointSet::new(maze.size());
+        let mut crossing = vec![false; maze.size()];
+
+        let attempts = (maze.size() as f64 * self.crossings).round() as usize;
+        for _ in 0..attempts {
+            let index = match maze.random_cell(rng) {
+                Some(index) => index,
+                None => break,
+            };
+
+            // The crossing needs all four neighbours, none of which can be a crossing themselves.
+            let nbs: Vec<usize> = [N, E, S, W]
+                .iter()
+                .filter_map(|&d| maze.get_index_relative(index, d))
+                .collect();
+            if crossing[index] || nbs.len() < 4 || nbs.iter().any(|&nb| crossing[nb]) {
+                continue;
+            }
+
+            // Both passages must join Cells that aren't already connected, or they would make a loop. The ends of each passage
+            // can't be direct neighbours either, which happens across a wrapped side 3 Cells long, or the tunnel would be a plain link.
+            let (n, e, s, w) = (nbs[0], nbs[1], nbs[2], nbs[3]);
+            if sets.find(n) == sets.find(s) || sets.find(e) == sets.find(w) {
+                continue;
+            }
+            if maze.is_direct_neighbour(n, s) || maze.is_direct_neighbour(e, w) {
+                continue;
+            }
+
+            // Pick which passage runs over the Cell. The other tunnels underneath it.
+            let (over, under) = match rng.gen() {
+                true => ((w, e), (n, s)),
+                false => ((n, s), (w, e)),
+            };
+
+            for (a, b) in [(over.0, index), (index, over.1), under] {
+                Grid::link(maze, a, b);
+                sets.union(a, b);
+                emit(Step::Link(a, b));
+            }
+            crossing[index] = true;
+        }
+
+        // Build a list of every interior wall that doesn't touch a crossing. Each wall is stored once, by the cell to its west or north.
+        let mut walls: Vec<(usize, usize)> = Vec::new();
+        for index in (0..maze.size()).filter(|&index| !crossing[index]) {
+            for &d in &[E, S] {
+                if let Some(nb_index) = maze.get_index_relative(index, d) {
+                    if !crossing[nb_index] {
+                        walls.push((index, nb_index));
+                    }
+                }
+            }
+        }
+
+        walls.shuffle(rng);
+
+        for (index, nb_index) in walls {
+            // Only carve the wall if the cells on either side aren't already connected.
+            if sets.union(index, nb_index) {
+                Grid::link(maze, index, nb_index);
+                emit(Step::Link(index, nb_index));
+            }
+        }
+    }
+}
@@ -2,69 +2,95 @@
 use rand::prelude::*;
 
 // Self imports
-use crate::{Direction, Generator, RectGrid};
+use crate::{Generator, Grid, Step};
 
 #[derive(Debug)]
 pub struct Wilsons {}
 
-impl Generator for Wilsons {
-    fn gen(&self, maze: &mut RectGrid) {
-        // Initialise random number generator
-        let mut rng = thread_rng();
-
-        // Build vector of unvisited cell indices and counter for number of visited cells.
-        let mut visited = 0;
-        let mut unvisited = vec![true; maze.grid.len()];
-
-        // Choose a random cell and set it as visited.
-        let index = rng.gen_range(0, unvisited.len());
-        unvisited[index] = false;
-        visited += 1;
-
-        let mut path: Vec<(usize, Direction)> = Vec::new();
-
-        // Loop until all cells are visited.
-        while visited < unvisited.len() {
-            // Get a random cell and initialise a new path.
-            let mut index = rng.gen_range(0, unvisited.len());
-            let mut cell_rc = maze.get_cell(index).unwrap();
-
-            // Loop while the chosen cell is unvisited.
-            while unvisited[index] {
-                // Pick a direction from list of directions a neighbour lies in.
-                let nb_dirs = cell_rc.borrow().get_neighbours();
-                let nb_dir = nb_dirs.choose(&mut rng).unwrap();
-
-                // Get the neighbour and it's index.
-                let nb_index = maze.get_index_relative(index, *nb_dir).unwrap();
-                let nb_rc = cell_rc.borrow().get_neighbour(*nb_dir).unwrap();
-
-                // Check if the chosen neighbour has been visited before.
-                // If yes, move path back to when it first occured.
-                // If no, add new neighbour to the path.
-                if let Some(p) = path.iter().position(|(i, _)| *i == nb_index) {
-                    path = (&path[0..p]).to_vec();
-                } else {
-                    path.push((index, *nb_dir));
-                }
+impl<G: Grid> Generator<G> for Wilsons {
+    fn gen_with_events(&self, maze: &mut G, rng: &mut dyn RngCore, emit: &mut dyn FnMut(Step)) {
+        // Build vector of unvisited cell indices and counter for number of visited cells. Masked cells count as visited.
+        let mut unvisited: Vec<bool> = (0..maze.size()).map(|i| !maze.is_masked(i)).collect();
+        let mut visited = unvisited.iter().filter(|&&u| !u).count();
 
-                // Set neighbour as the current cell.
-                cell_rc = nb_rc;
-                index = nb_index;
-            }
+        // Set a random cell in every region as visited.
+        for (start, _) in maze.region_starts(rng) {
+            unvisited[start] = false;
+            visited += 1;
+            emit(Step::Visit(start));
+        }
 
-            for (index, dir) in &path {
-                // Get the cell and link it in specified direction.
-                let cell_rc = maze.get_cell(*index).unwrap();
-                let mut cell = cell_rc.borrow_mut();
-                cell.link(*dir);
+        loop_erased_walks(maze, &mut unvisited, visited, rng, emit);
+    }
+}
+
+/// Run loop-erased random walks from random unvisited cells until every cell is visited.
+/// Each walk ends when it reaches a visited cell, so every region of the maze must already have a visited cell. 'visited' is the number of visited cells.
+pub(crate) fn loop_erased_walks<G: Grid>(
+    maze: &mut G,
+    unvisited: &mut [bool],
+    mut visited: usize,
+    rng: &mut dyn RngCore,
+    emit: &mut dyn FnMut(Step),
+) {
+    // The neighbour the walk last moved to from each cell. Following these from the start of a walk gives the loop-erased path.
+    let mut exits: Vec<Option<usize>> = vec![None; maze.size()];
+
+    // Cells on the current loop-erased path, in order, and a lookup for whether a cell is on it.
+    let mut path: Vec<usize> = Vec::new();
+    let mut on_path = vec![false; maze.size()];
+
+    // Loop until all cells are visited.
+    while visited < unvisited.len() {
+        // Get a random cell to start a new walk from.
+        let start = rng.gen_range(0, unvisited.len());
+        if !unvisited[start] {
+            continue;
+        }
 
-                // Set cell as visited.
-                unvisited[*index] = false;
-                visited += 1;
+        let mut index = start;
+        path.push(start);
+        on_path[start] = true;
+        emit(Step::Visit(start));
+
+        // Walk until a visited cell is reached. Revisiting a cell overwrites its exit, which erases the loop.
+        while unvisited[index] {
+            // Pick a random neighbour.
+            let nb_index = *maze.neighbours(index).choose(rng).unwrap();
+
+            exits[index] = Some(nb_index);
+            index = nb_index;
+
+            // Erase the loop from the path if the walk crossed itself. Otherwise extend the path.
+            if on_path[index] {
+                while path.last() != Some(&index) {
+                    let erased = path.pop().unwrap();
+                    on_path[erased] = false;
+                    emit(Step::Backtrack(erased));
+                }
+            } else if unvisited[index] {
+                path.push(index);
+                on_path[index] = true;
             }
+            emit(Step::Visit(index));
+        }
+
+        // Retrace the walk from the start using the last exit from each cell, linking as we go.
+        let mut index = start;
+        while unvisited[index] {
+            let nb_index = exits[index].unwrap();
+
+            // Link the cell to the next cell of the walk.
+            maze.link(index, nb_index);
+            emit(Step::Link(index, nb_index));
+
+            // Set cell as visited.
+            unvisited[index] = false;
+            on_path[index] = false;
+            visited += 1;
 
-            path.clear();
+            index = nb_index;
         }
+        path.clear();
     }
 }
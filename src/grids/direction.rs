@@ -0,0 +1,115 @@
+/// A cardinal direction (North, South, East, West). Used to point to a Cell's neighbours.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// Every Direction, in the order neighbours are listed in.
+    pub const ALL: [Direction; 4] = [N, E, S, W];
+
+    /// Return the Direction pointing the opposite way.
+    pub fn opposite(self) -> Direction {
+        match self {
+            N => S,
+            S => N,
+            E => W,
+            W => E,
+        }
+    }
+
+    /// Return the bit used to store a link in this Direction.
+    pub fn bit(self) -> u8 {
+        match self {
+            N => 0b0001,
+            E => 0b0010,
+            S => 0b0100,
+            W => 0b1000,
+        }
+    }
+}
+
+/// A direction on a HexGrid. Hexagons have flat tops, so every Cell has a neighbour to the North and South and four on the diagonals.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl HexDirection {
+    /// Every HexDirection, in the order neighbours are listed in (clockwise from North).
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::N,
+        HexDirection::NE,
+        HexDirection::SE,
+        HexDirection::S,
+        HexDirection::SW,
+        HexDirection::NW,
+    ];
+
+    /// Return the HexDirection pointing the opposite way.
+    pub fn opposite(self) -> HexDirection {
+        match self {
+            HexDirection::N => HexDirection::S,
+            HexDirection::NE => HexDirection::SW,
+            HexDirection::SE => HexDirection::NW,
+            HexDirection::S => HexDirection::N,
+            HexDirection::SW => HexDirection::NE,
+            HexDirection::NW => HexDirection::SE,
+        }
+    }
+
+    /// Return the bit used to store a link in this HexDirection.
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A direction in a Grid3D. The four cardinal directions move within a level, Up and Down move between levels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction3D {
+    N,
+    E,
+    S,
+    W,
+    Up,
+    Down,
+}
+
+impl Direction3D {
+    /// Every Direction3D, in the order neighbours are listed in.
+    pub const ALL: [Direction3D; 6] = [
+        Direction3D::N,
+        Direction3D::E,
+        Direction3D::S,
+        Direction3D::W,
+        Direction3D::Up,
+        Direction3D::Down,
+    ];
+
+    /// Return the Direction3D pointing the opposite way.
+    pub fn opposite(self) -> Direction3D {
+        match self {
+            Direction3D::N => Direction3D::S,
+            Direction3D::E => Direction3D::W,
+            Direction3D::S => Direction3D::N,
+            Direction3D::W => Direction3D::E,
+            Direction3D::Up => Direction3D::Down,
+            Direction3D::Down => Direction3D::Up,
+        }
+    }
+
+    /// Return the bit used to store a link in this Direction3D.
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
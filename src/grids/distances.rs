@@ -0,0 +1,99 @@
+// Self imports
+use crate::{Grid, RectGrid};
+
+/// The distance from one or more start Cells to every Cell of a RectGrid, following the links between Cells.
+/// Cells that can't be reached (including masked and solid Cells) have no distance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distances {
+    cols: usize,
+
+    // Distance to every Cell, indexed in the same way as the RectGrid.
+    distances: Vec<Option<usize>>,
+}
+
+impl Distances {
+    /// Calculate the distance to every Cell of the maze from the closest of the Cells in 'starts'.
+    /// Starts that are masked or solid aren't part of the maze and are ignored.
+    pub fn new(maze: &RectGrid, starts: &[usize]) -> Self {
+        let starts: Vec<usize> = starts
+            .iter()
+            .copied()
+            .filter(|&index| index < maze.size() && !maze.is_masked(index) && !maze.is_solid(index))
+            .collect();
+
+        Distances {
+            cols: maze.cols,
+            distances: maze.distances_from(&starts),
+        }
+    }
+
+    /// Return the distance to the Cell at the given index. Returns None if it can't be reached or doesn't exist.
+    pub fn get(&self, index: usize) -> Option<usize> {
+        self.distances.get(index).copied().flatten()
+    }
+
+    /// Return the distance to the Cell at (row, col). Returns None if it can't be reached or doesn't exist.
+    pub fn get_at(&self, row: usize, col: usize) -> Option<usize> {
+        if col >= self.cols {
+            return None;
+        }
+        self.get(row * self.cols + col)
+    }
+
+    /// Return the largest distance to any Cell that can be reached. Returns None if no Cells can be reached.
+    pub fn max(&self) -> Option<usize> {
+        self.distances.iter().flatten().copied().max()
+    }
+
+    /// Return the index of the Cell farthest from the start. Ties go to the Cell with the lowest index.
+    /// Returns None if no Cells can be reached.
+    pub fn farthest(&self) -> Option<usize> {
+        let max = self.max()?;
+        self.distances.iter().position(|&d| d == Some(max))
+    }
+
+    /// Return the index of the Cell out of 'cells' that is farthest from the start. Ties go to the earliest Cell in 'cells'.
+    /// Returns None if none of them can be reached.
+    pub fn farthest_of(&self, cells: &[usize]) -> Option<usize> {
+        cells
+            .iter()
+            .filter_map(|&index| self.get(index).map(|d| (d, index)))
+            .fold(
+                None,
+                |best: Option<(usize, usize)>, (d, index)| match best {
+                    Some((best_d, _)) if best_d >= d => best,
+                    _ => Some((d, index)),
+                },
+            )
+            .map(|(_, index)| index)
+    }
+
+    /// Return the shortest path through the maze from the closest start to 'goal', including both ends.
+    /// Walks back from 'goal' through linked Cells that are one step closer. Returns an empty path if 'goal' can't be reached.
+    pub fn path_to(&self, maze: &RectGrid, goal: usize) -> Vec<usize> {
+        let mut distance = match self.get(goal) {
+            Some(distance) => distance,
+            None => return Vec::new(),
+        };
+
+        let mut path = vec![goal];
+        let mut index = goal;
+        while distance > 0 {
+            distance -= 1;
+            index = maze
+                .links(index)
+                .into_iter()
+                .find(|&nb_index| self.get(nb_index) == Some(distance))
+                .expect("distances don't match the maze");
+            path.push(index);
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Return the distance to every Cell, indexed in the same way as the RectGrid.
+    pub fn as_slice(&self) -> &[Option<usize>] {
+        &self.distances
+    }
+}
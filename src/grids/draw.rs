@@ -0,0 +1,144 @@
+// External imports
+use colorous;
+use image::{Rgb, RgbImage};
+
+/// Return a function giving the colour of the Cell at each index from its distance. Cells without a distance aren't coloured.
+pub(crate) fn distance_colours(
+    distances: &[Option<usize>],
+) -> impl Fn(usize) -> Option<Rgb<u8>> + '_ {
+    let cycles = 1;
+    let gradient = colorous::MAGMA;
+    let max = 1 + distances.iter().flatten().max().unwrap_or(&0) / cycles;
+
+    move |index| {
+        let distance = (*distances.get(index)?)?;
+        let colour = gradient.eval_rational(max - (distance % max), max);
+        Some(Rgb([colour.r, colour.g, colour.b]))
+    }
+}
+
+/// Draw a maze with 'draw', passing it the colour of each Cell from 'distances', and resize the drawing to 'final_x' by 'final_y'.
+/// Shared by the 'create_image' method of every grid.
+pub(crate) fn render_image<D>(
+    distances: &[Option<usize>],
+    final_x: u32,
+    final_y: u32,
+    draw: D,
+) -> RgbImage
+where
+    D: FnOnce(&dyn Fn(usize) -> Option<Rgb<u8>>) -> RgbImage,
+{
+    let imgbuf = draw(&distance_colours(distances));
+
+    image::imageops::resize(
+        &imgbuf,
+        final_x,
+        final_y,
+        image::imageops::FilterType::Nearest,
+    )
+}
+
+/// Draw a one pixel wide line between two points. Points outside the image are skipped.
+pub(crate) fn draw_line(
+    img: &mut RgbImage,
+    (x1, y1): (f64, f64),
+    (x2, y2): (f64, f64),
+    colour: Rgb<u8>,
+) {
+    let (mut x, mut y) = (x1.round() as i64, y1.round() as i64);
+    let (x2, y2) = (x2.round() as i64, y2.round() as i64);
+
+    // Bresenham's line algorithm.
+    let dx = (x2 - x).abs();
+    let dy = -(y2 - y).abs();
+    let sx = if x < x2 { 1 } else { -1 };
+    let sy = if y < y2 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    loop {
+        put_pixel(img, x, y, colour);
+        if x == x2 && y == y2 {
+            break;
+        }
+
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// Draw an arc of the circle with the given centre and radius, clockwise from angle 'theta1' to 'theta2' (radians from east).
+pub(crate) fn draw_arc(
+    img: &mut RgbImage,
+    (cx, cy): (f64, f64),
+    radius: f64,
+    theta1: f64,
+    theta2: f64,
+    colour: Rgb<u8>,
+) {
+    // Approximate the arc with straight lines roughly a pixel long.
+    let steps = (radius * (theta2 - theta1).abs()).ceil().max(1.0) as usize;
+    let point = |i: usize| {
+        let theta = theta1 + (theta2 - theta1) * i as f64 / steps as f64;
+        (cx + radius * theta.cos(), cy + radius * theta.sin())
+    };
+
+    for i in 0..steps {
+        draw_line(img, point(i), point(i + 1), colour);
+    }
+}
+
+/// Fill the polygon with the given corners. Pixels are filled if their centre lies inside the polygon.
+pub(crate) fn fill_polygon(img: &mut RgbImage, points: &[(f64, f64)], colour: Rgb<u8>) {
+    if points.is_empty() {
+        return;
+    }
+
+    let min_y = points
+        .iter()
+        .map(|p| p.1)
+        .fold(f64::INFINITY, f64::min)
+        .floor()
+        .max(0.0) as u32;
+    let max_y = points
+        .iter()
+        .map(|p| p.1)
+        .fold(0.0, f64::max)
+        .ceil()
+        .min(img.height() as f64) as u32;
+
+    // Scanline fill. Find where each row of pixel centres crosses the edges and fill between pairs of crossings.
+    for y in min_y..max_y {
+        let cy = y as f64 + 0.5;
+        let mut crossings: Vec<f64> = Vec::new();
+
+        for (i, &(x1, y1)) in points.iter().enumerate() {
+            let (x2, y2) = points[(i + 1) % points.len()];
+            if (y1 <= cy) != (y2 <= cy) {
+                crossings.push(x1 + (cy - y1) / (y2 - y1) * (x2 - x1));
+            }
+        }
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for pair in crossings.chunks_exact(2) {
+            let start = (pair[0] - 0.5).ceil().max(0.0) as i64;
+            let end = (pair[1] - 0.5).floor() as i64;
+            for x in start..=end {
+                put_pixel(img, x, y as i64, colour);
+            }
+        }
+    }
+}
+
+/// Colour a single pixel if it lies within the image.
+fn put_pixel(img: &mut RgbImage, x: i64, y: i64, colour: Rgb<u8>) {
+    if x >= 0 && y >= 0 && (x as u32) < img.width() && (y as u32) < img.height() {
+        img.put_pixel(x as u32, y as u32, colour);
+    }
+}
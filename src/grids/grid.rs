@@ -0,0 +1,135 @@
+// External imports
+use rand::{Rng, RngCore};
+
+// Standard imports
+use std::fmt;
+
+/// The shape of a maze. Cells are identified by an index from 0 to 'size' and are linked to their neighbours to carve passages.
+/// Generators that only need to walk from Cell to Cell work on any Grid. Links are always stored on both Cells.
+pub trait Grid: Clone + fmt::Debug + Send + Sync {
+    /// Return the number of Cells in the grid.
+    fn size(&self) -> usize;
+
+    /// Return the indices of the neighbours of the Cell at the given index. The order is fixed for each kind of Grid.
+    fn neighbours(&self, index: usize) -> Vec<usize>;
+
+    /// Return the indices of the neighbours the Cell at the given index is linked to, in the same order as 'neighbours'.
+    fn links(&self, index: usize) -> Vec<usize>;
+
+    /// Return true if the Cells at the two indices are linked.
+    fn is_linked(&self, a: usize, b: usize) -> bool;
+
+    /// Link the Cells at the two indices. Panics if they aren't neighbours.
+    fn link(&mut self, a: usize, b: usize);
+
+    /// Unlink the Cells at the two indices. Panics if they aren't neighbours.
+    fn unlink(&mut self, a: usize, b: usize);
+
+    /// Return the index of a random Cell using the given random number generator. Returns None if the grid has no Cells.
+    fn random_cell(&self, rng: &mut dyn RngCore) -> Option<usize>;
+
+    /// Return true if the Cell at the given index has been masked off. Masked Cells have no neighbours and are never part of the maze.
+    fn is_masked(&self, _index: usize) -> bool {
+        false
+    }
+
+    /// Return the neighbours of the Cell at the given index that aren't linked to anything.
+    fn unlinked_neighbours(&self, index: usize) -> Vec<usize> {
+        self.neighbours(index)
+            .into_iter()
+            .filter(|&nb| self.links(nb).is_empty())
+            .collect()
+    }
+
+    /// Return the neighbours of the Cell at the given index that are linked to something.
+    fn linked_neighbours(&self, index: usize) -> Vec<usize> {
+        self.neighbours(index)
+            .into_iter()
+            .filter(|&nb| !self.links(nb).is_empty())
+            .collect()
+    }
+
+    /// Pick a random start Cell in every region of the grid. Returns the start and the number of Cells in the region for each one.
+    /// A region is a group of Cells that aren't masked and can all reach each other through neighbours. A Mask can cut a maze into
+    /// several regions, and walks or growing trees can never leave the region they start in, so Generators carve each region from
+    /// a start of its own. A grid with no masked Cells is a single region and its start comes straight from 'random_cell'.
+    fn region_starts(&self, rng: &mut dyn RngCore) -> Vec<(usize, usize)> {
+        if (0..self.size()).all(|i| !self.is_masked(i)) {
+            return self
+                .random_cell(rng)
+                .map(|start| (start, self.size()))
+                .into_iter()
+                .collect();
+        }
+
+        let mut seen: Vec<bool> = (0..self.size()).map(|i| self.is_masked(i)).collect();
+        let mut queue = Vec::new();
+        let mut starts = Vec::new();
+
+        for first in 0..self.size() {
+            if seen[first] {
+                continue;
+            }
+
+            // Breadth first search from the first Cell that isn't in a region yet. The start is picked by reservoir sampling,
+            // replacing it with the nth Cell found with probability 1/n, so the Cells of the region never need to be stored.
+            seen[first] = true;
+            queue.clear();
+            queue.push(first);
+            let (mut start, mut next) = (first, 0);
+            while next < queue.len() {
+                let index = queue[next];
+                next += 1;
+                if rng.gen_range(0, next) == 0 {
+                    start = index;
+                }
+
+                for nb_index in self.neighbours(index) {
+                    if !seen[nb_index] {
+                        seen[nb_index] = true;
+                        queue.push(nb_index);
+                    }
+                }
+            }
+            starts.push((start, next));
+        }
+        starts
+    }
+
+    /// Breadth first search from the Cell at 'start'. Returns the distance to every Cell, or None for Cells that can't be reached.
+    fn distances(&self, start: usize) -> Vec<Option<usize>> {
+        self.distances_from(&[start])
+    }
+
+    /// Breadth first search from every Cell in 'starts' at once. Returns the distance to every Cell from the closest start,
+    /// or None for Cells that can't be reached. Indices outside the grid are ignored.
+    fn distances_from(&self, starts: &[usize]) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.size()];
+
+        // Place start cells in "current" vec
+        let mut current = Vec::new();
+        for &start in starts {
+            if start < distances.len() && distances[start].is_none() {
+                distances[start] = Some(0);
+                current.push(start);
+            }
+        }
+
+        let mut distance = 0;
+        while !current.is_empty() {
+            let mut next = Vec::new();
+            distance += 1;
+
+            for index in current {
+                for nb_index in self.links(index) {
+                    if distances[nb_index].is_none() {
+                        distances[nb_index] = Some(distance);
+                        next.push(nb_index);
+                    }
+                }
+            }
+            current = next;
+        }
+        distances
+    }
+}
@@ -0,0 +1,216 @@
+// External imports
+use image;
+use rand::{Rng, RngCore};
+
+// Self imports
+use crate::grids::draw::{draw_line, fill_polygon, render_image};
+use crate::{Direction3D, Grid};
+
+/// A maze with several levels stacked on top of each other. Every level is a grid of square Cells and Cells can also link
+/// Up and Down to the Cell directly above or below them. Cells are identified by their index (level * rows * cols + row * cols + col).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid3D {
+    pub levels: usize,
+    pub rows: usize,
+    pub cols: usize,
+
+    // Link bits for every Cell. A Cell is linked in a Direction3D if 'Direction3D::bit' is set. Links are stored on both Cells.
+    links: Vec<u8>,
+}
+
+impl Grid3D {
+    /// Create a maze of given size. All Cells will be completely surrounded by walls.
+    pub fn new(levels: usize, rows: usize, cols: usize) -> Self {
+        Grid3D {
+            levels,
+            rows,
+            cols,
+            links: vec![0; levels * rows * cols],
+        }
+    }
+
+    /// Return the index of the Cell at the given level, row and column.
+    pub fn index(&self, level: usize, row: usize, col: usize) -> usize {
+        (level * self.rows + row) * self.cols + col
+    }
+
+    /// Return the level, row and column of the Cell at the given index.
+    pub fn position(&self, index: usize) -> (usize, usize, usize) {
+        let level_size = self.rows * self.cols;
+        let (level, rest) = (index / level_size, index % level_size);
+        (level, rest / self.cols, rest % self.cols)
+    }
+
+    /// Given the index of the current cell and a Direction3D, return the index of the Cell that exists in that direction.
+    /// Return None if Cell in that direction doesn't exist.
+    pub fn get_index_relative(&self, index: usize, d: Direction3D) -> Option<usize> {
+        let (level, row, col) = self.position(index);
+
+        let (level, row, col) = match d {
+            Direction3D::N if row > 0 => (level, row - 1, col),
+            Direction3D::E if col + 1 < self.cols => (level, row, col + 1),
+            Direction3D::S if row + 1 < self.rows => (level, row + 1, col),
+            Direction3D::W if col > 0 => (level, row, col - 1),
+            Direction3D::Up if level + 1 < self.levels => (level + 1, row, col),
+            Direction3D::Down if level > 0 => (level - 1, row, col),
+            _ => return None,
+        };
+        Some(self.index(level, row, col))
+    }
+
+    /// Return true if the Cell at the given index is linked to its neighbour in the given Direction3D.
+    pub fn is_linked(&self, index: usize, d: Direction3D) -> bool {
+        self.links[index] & d.bit() != 0
+    }
+
+    /// Return the Direction3D of neighbour 'b' from Cell 'a'. Panics if they aren't neighbours.
+    fn direction_between(&self, a: usize, b: usize) -> Direction3D {
+        *Direction3D::ALL
+            .iter()
+            .find(|&&d| self.get_index_relative(a, d) == Some(b))
+            .expect("cells are not neighbours")
+    }
+
+    /// Create an ImageBuffer from the maze. If 'colour' is true, Cells are coloured by their distance from the top left Cell
+    /// of the bottom level, following stairs between levels.
+    pub fn create_image(
+        &self,
+        cell_size: usize,
+        final_x: u32,
+        final_y: u32,
+        colour: bool,
+    ) -> image::RgbImage {
+        let distances = if colour {
+            self.distances(0)
+        } else {
+            Vec::new()
+        };
+
+        render_image(&distances, final_x, final_y, |cell_bg| {
+            self.draw(cell_size, cell_bg)
+        })
+    }
+
+    /// Draw every level side by side, from the bottom level on the left to the top level on the right, with each Cell taking up
+    /// 'cell_size' pixels. Links Up are marked with an arrow pointing up in the right of the Cell and links Down with an arrow
+    /// pointing down in the left of the Cell. 'cell_bg' gives the background colour of the Cell at each index.
+    /// Cells it returns None for are left white.
+    pub fn draw<F>(&self, cell_size: usize, cell_bg: F) -> image::RgbImage
+    where
+        F: Fn(usize) -> Option<image::Rgb<u8>>,
+    {
+        // Levels are separated by a gap one Cell wide.
+        let level_width = cell_size * self.cols;
+        let img_x = (level_width + cell_size) * self.levels.max(1) - cell_size + 1;
+        let img_y = cell_size * self.rows + 1;
+
+        // Set colours.
+        let bg = image::Rgb([255, 255, 255]);
+        let wall = image::Rgb([0, 0, 0]);
+        let stairs = image::Rgb([40, 110, 220]);
+
+        // Create ImageBuffer.
+        let mut imgbuf = image::ImageBuffer::from_pixel(img_x as u32, img_y as u32, bg);
+
+        for index in 0..self.size() {
+            let (level, row, col) = self.position(index);
+
+            let x1 = (level * (level_width + cell_size) + col * cell_size) as f64;
+            let y1 = (row * cell_size) as f64;
+            let x2 = x1 + cell_size as f64;
+            let y2 = y1 + cell_size as f64;
+
+            // Draw cell background.
+            if let Some(cell_bg) = cell_bg(index) {
+                let corners = [
+                    (x1, y1),
+                    (x2 - 1.0, y1),
+                    (x2 - 1.0, y2 - 1.0),
+                    (x1, y2 - 1.0),
+                ];
+                fill_polygon(&mut imgbuf, &corners, cell_bg);
+            }
+
+            // Draw line for North wall of cell and, for the top row, the boundary.
+            if !self.is_linked(index, Direction3D::N) {
+                draw_line(&mut imgbuf, (x1, y1), (x2, y1), wall);
+            }
+
+            // Draw line for East wall of cell.
+            if !self.is_linked(index, Direction3D::E) {
+                draw_line(&mut imgbuf, (x2, y1), (x2, y2), wall);
+            }
+
+            // Draw Boundary. West wall of cells in the first column and South wall of cells in the last row.
+            if col == 0 {
+                draw_line(&mut imgbuf, (x1, y1), (x1, y2), wall);
+            }
+            if row + 1 == self.rows {
+                draw_line(&mut imgbuf, (x1, y2), (x2, y2), wall);
+            }
+
+            // Draw arrows for stairs. Each arrow fills a third of the width of the Cell.
+            let third = cell_size as f64 / 3.0;
+            let (top, bottom) = (y1 + third, y2 - third);
+
+            if self.is_linked(index, Direction3D::Up) {
+                let (left, right) = (x2 - third - third / 2.0, x2 - third / 2.0);
+                let arrow = [((left + right) / 2.0, top), (right, bottom), (left, bottom)];
+                fill_polygon(&mut imgbuf, &arrow, stairs);
+            }
+            if self.is_linked(index, Direction3D::Down) {
+                let (left, right) = (x1 + third / 2.0, x1 + third + third / 2.0);
+                let arrow = [(left, top), (right, top), ((left + right) / 2.0, bottom)];
+                fill_polygon(&mut imgbuf, &arrow, stairs);
+            }
+        }
+        imgbuf
+    }
+}
+
+impl Grid for Grid3D {
+    fn size(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Neighbours are ordered N, E, S, W, Up, Down.
+    fn neighbours(&self, index: usize) -> Vec<usize> {
+        Direction3D::ALL
+            .iter()
+            .filter_map(|&d| self.get_index_relative(index, d))
+            .collect()
+    }
+
+    fn links(&self, index: usize) -> Vec<usize> {
+        Direction3D::ALL
+            .iter()
+            .filter(|&&d| self.is_linked(index, d))
+            .filter_map(|&d| self.get_index_relative(index, d))
+            .collect()
+    }
+
+    fn is_linked(&self, a: usize, b: usize) -> bool {
+        Direction3D::ALL
+            .iter()
+            .any(|&d| self.is_linked(a, d) && self.get_index_relative(a, d) == Some(b))
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        let d = self.direction_between(a, b);
+        self.links[a] |= d.bit();
+        self.links[b] |= d.opposite().bit();
+    }
+
+    fn unlink(&mut self, a: usize, b: usize) {
+        let d = self.direction_between(a, b);
+        self.links[a] &= !d.bit();
+        self.links[b] &= !d.opposite().bit();
+    }
+
+    fn random_cell(&self, rng: &mut dyn RngCore) -> Option<usize> {
+        if self.links.is_empty() {
+            return None;
+        }
+        Some(rng.gen_range(0, self.links.len()))
+    }
+}
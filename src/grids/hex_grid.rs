@@ -0,0 +1,207 @@
+// External imports
+use image;
+use rand::{Rng, RngCore};
+
+// Self imports
+use crate::grids::draw::{draw_line, fill_polygon, render_image};
+use crate::{Grid, HexDirection};
+
+/// A maze made of flat topped hexagons. Cells are identified by their index (row * cols + col) like a RectGrid.
+/// Odd columns are shifted half a Cell down, so which row a diagonal neighbour is in depends on the column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid {
+    pub rows: usize,
+    pub cols: usize,
+
+    // Link bits for every Cell. A Cell is linked in a HexDirection if 'HexDirection::bit' is set. Links are stored on both Cells.
+    links: Vec<u8>,
+}
+
+impl HexGrid {
+    /// Create a maze of given size. All Cells will be completely surrounded by walls.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        HexGrid {
+            rows,
+            cols,
+            links: vec![0; rows * cols],
+        }
+    }
+
+    /// Given the index of the current cell and a HexDirection, return the index of the Cell that exists in that direction.
+    /// Return None if Cell in that direction doesn't exist.
+    pub fn get_index_relative(&self, index: usize, d: HexDirection) -> Option<usize> {
+        let (row, col) = ((index / self.cols) as isize, (index % self.cols) as isize);
+
+        // Even columns sit half a Cell higher than odd ones. Their diagonal neighbours to the north are one row up.
+        let (north_diagonal, south_diagonal) = match col % 2 {
+            0 => (row - 1, row),
+            _ => (row, row + 1),
+        };
+
+        let (row, col) = match d {
+            HexDirection::N => (row - 1, col),
+            HexDirection::NE => (north_diagonal, col + 1),
+            HexDirection::SE => (south_diagonal, col + 1),
+            HexDirection::S => (row + 1, col),
+            HexDirection::SW => (south_diagonal, col - 1),
+            HexDirection::NW => (north_diagonal, col - 1),
+        };
+
+        if row < 0 || col < 0 || row as usize >= self.rows || col as usize >= self.cols {
+            return None;
+        }
+        Some(row as usize * self.cols + col as usize)
+    }
+
+    /// Return true if the Cell at the given index is linked to its neighbour in the given HexDirection.
+    pub fn is_linked(&self, index: usize, d: HexDirection) -> bool {
+        self.links[index] & d.bit() != 0
+    }
+
+    /// Return the HexDirection of neighbour 'b' from Cell 'a'. Panics if they aren't neighbours.
+    fn direction_between(&self, a: usize, b: usize) -> HexDirection {
+        *HexDirection::ALL
+            .iter()
+            .find(|&&d| self.get_index_relative(a, d) == Some(b))
+            .expect("cells are not neighbours")
+    }
+
+    /// Create an ImageBuffer from the maze. If 'colour' is true, Cells are coloured by their distance from the top left Cell.
+    pub fn create_image(
+        &self,
+        cell_size: usize,
+        final_x: u32,
+        final_y: u32,
+        colour: bool,
+    ) -> image::RgbImage {
+        let distances = if colour {
+            self.distances(0)
+        } else {
+            Vec::new()
+        };
+
+        render_image(&distances, final_x, final_y, |cell_bg| {
+            self.draw(cell_size, cell_bg)
+        })
+    }
+
+    /// Draw the maze. 'cell_size' is the distance in pixels from the centre of each hexagon to its corners.
+    /// 'cell_bg' gives the background colour of the Cell at each index. Cells it returns None for are left white.
+    pub fn draw<F>(&self, cell_size: usize, cell_bg: F) -> image::RgbImage
+    where
+        F: Fn(usize) -> Option<image::Rgb<u8>>,
+    {
+        // Half the width of a hexagon's flat top, and half its height.
+        let size = cell_size as f64;
+        let a_size = size / 2.0;
+        let b_size = size * 3f64.sqrt() / 2.0;
+
+        // Set image dimensions. Columns overlap by a quarter of a hexagon and odd columns stick out half a Cell at the bottom.
+        let img_x = (3.0 * a_size * self.cols as f64 + a_size).ceil() as u32 + 1;
+        let img_y = (2.0 * b_size * self.rows as f64 + b_size).ceil() as u32 + 1;
+
+        // Set colours.
+        let bg = image::Rgb([255, 255, 255]);
+        let wall = image::Rgb([0, 0, 0]);
+
+        // Create ImageBuffer.
+        let mut imgbuf = image::ImageBuffer::from_pixel(img_x, img_y, bg);
+
+        // Corners of the hexagon for every Cell, clockwise from the far west corner.
+        let corners = |index: usize| {
+            let (row, col) = (index / self.cols, index % self.cols);
+            let cx = size + 3.0 * col as f64 * a_size;
+            let cy = b_size + row as f64 * 2.0 * b_size + (col % 2) as f64 * b_size;
+
+            [
+                (cx - size, cy),
+                (cx - a_size, cy - b_size),
+                (cx + a_size, cy - b_size),
+                (cx + size, cy),
+                (cx + a_size, cy + b_size),
+                (cx - a_size, cy + b_size),
+            ]
+        };
+
+        // Draw cell backgrounds.
+        for index in 0..self.size() {
+            if let Some(cell_bg) = cell_bg(index) {
+                fill_polygon(&mut imgbuf, &corners(index), cell_bg);
+            }
+        }
+
+        for index in 0..self.size() {
+            let [fw, nw, ne, fe, se, sw] = corners(index);
+
+            // Walls between two Cells are drawn by the Cell on their west side (or north side for the South wall).
+            // Walls on the boundary are drawn by the only Cell they belong to.
+            let walls = [
+                (HexDirection::N, nw, ne),
+                (HexDirection::NE, ne, fe),
+                (HexDirection::SE, fe, se),
+                (HexDirection::S, se, sw),
+                (HexDirection::SW, sw, fw),
+                (HexDirection::NW, fw, nw),
+            ];
+
+            for &(d, from, to) in &walls {
+                let neighbour = self.get_index_relative(index, d);
+                let owns_wall = match d {
+                    HexDirection::NE | HexDirection::SE | HexDirection::S => true,
+                    _ => neighbour.is_none(),
+                };
+
+                if owns_wall && !self.is_linked(index, d) {
+                    draw_line(&mut imgbuf, from, to, wall);
+                }
+            }
+        }
+        imgbuf
+    }
+}
+
+impl Grid for HexGrid {
+    fn size(&self) -> usize {
+        self.links.len()
+    }
+
+    fn neighbours(&self, index: usize) -> Vec<usize> {
+        HexDirection::ALL
+            .iter()
+            .filter_map(|&d| self.get_index_relative(index, d))
+            .collect()
+    }
+
+    fn links(&self, index: usize) -> Vec<usize> {
+        HexDirection::ALL
+            .iter()
+            .filter(|&&d| self.is_linked(index, d))
+            .filter_map(|&d| self.get_index_relative(index, d))
+            .collect()
+    }
+
+    fn is_linked(&self, a: usize, b: usize) -> bool {
+        HexDirection::ALL
+            .iter()
+            .any(|&d| self.is_linked(a, d) && self.get_index_relative(a, d) == Some(b))
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        let d = self.direction_between(a, b);
+        self.links[a] |= d.bit();
+        self.links[b] |= d.opposite().bit();
+    }
+
+    fn unlink(&mut self, a: usize, b: usize) {
+        let d = self.direction_between(a, b);
+        self.links[a] &= !d.bit();
+        self.links[b] &= !d.opposite().bit();
+    }
+
+    fn random_cell(&self, rng: &mut dyn RngCore) -> Option<usize> {
+        if self.links.is_empty() {
+            return None;
+        }
+        Some(rng.gen_range(0, self.links.len()))
+    }
+}
@@ -0,0 +1,84 @@
+// External imports
+use image::{self, ImageResult};
+
+// Standard imports
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Marks which Cells of a RectGrid are part of the maze. Cells that are "off" have no neighbours, are never visited by Generators
+/// and are left blank when the maze is drawn, so the maze takes the shape of the Cells that are "on".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    pub rows: usize,
+    pub cols: usize,
+
+    // Whether each Cell is on, indexed in the same way as a RectGrid.
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    /// Create a Mask of given size with every Cell on.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Mask {
+            rows,
+            cols,
+            bits: vec![true; rows * cols],
+        }
+    }
+
+    /// Create a Mask from ASCII art. Every line is a row and every character a Cell. 'X' (or 'x') turns a Cell off.
+    /// Every other character, and any Cell past the end of a short line, is on.
+    pub fn from_ascii(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let cols = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut mask = Mask::new(lines.len(), cols);
+
+        for (row, line) in lines.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if c == 'X' || c == 'x' {
+                    mask.set(row, col, false);
+                }
+            }
+        }
+        mask
+    }
+
+    /// Load a Mask from a text file of ASCII art. See 'from_ascii' for the format.
+    pub fn from_txt<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Mask::from_ascii(&fs::read_to_string(path)?))
+    }
+
+    /// Load a Mask from an image with one pixel per Cell. Dark pixels turn a Cell off and light pixels leave it on.
+    pub fn from_png<P: AsRef<Path>>(path: P) -> ImageResult<Self> {
+        let img = image::open(path)?.into_luma8();
+        let (cols, rows) = (img.width() as usize, img.height() as usize);
+        let mut mask = Mask::new(rows, cols);
+
+        for (x, y, pixel) in img.enumerate_pixels() {
+            if pixel.0[0] < 128 {
+                mask.set(y as usize, x as usize, false);
+            }
+        }
+        Ok(mask)
+    }
+
+    /// Return true if the Cell at (row, col) is on.
+    pub fn is_on(&self, row: usize, col: usize) -> bool {
+        self.bits[row * self.cols + col]
+    }
+
+    /// Turn the Cell at (row, col) on or off.
+    pub fn set(&mut self, row: usize, col: usize, on: bool) {
+        self.bits[row * self.cols + col] = on;
+    }
+
+    /// Return the number of Cells that are on.
+    pub fn count(&self) -> usize {
+        self.bits.iter().filter(|&&on| on).count()
+    }
+}
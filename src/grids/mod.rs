@@ -1 +1,10 @@
+pub mod direction;
+pub mod distances;
+pub mod draw;
+pub mod grid;
+pub mod grid_3d;
+pub mod hex_grid;
+pub mod mask;
+pub mod polar_grid;
 pub mod rect_grid;
+pub mod triangle_grid;
@@ -0,0 +1,279 @@
+// External imports
+use image;
+use rand::{Rng, RngCore};
+
+// Standard imports
+use std::f64::consts::PI;
+
+// Self imports
+use crate::grids::draw::{draw_arc, draw_line, fill_polygon, render_image};
+use crate::Grid;
+
+/// A circular maze made of concentric rings. The centre is a single Cell and rings split their Cells in two
+/// whenever they would get too wide, so outer Cells stay roughly square. Cells are indexed ring by ring, starting from the centre,
+/// and clockwise within each ring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolarGrid {
+    pub rows: usize,
+
+    // Number of Cells in every ring and the index of the first Cell of every ring.
+    counts: Vec<usize>,
+    offsets: Vec<usize>,
+
+    // Link bits for every Cell. Bit 'n' is set if the Cell is linked to the nth Cell returned by 'neighbours'. Links are stored on both Cells.
+    links: Vec<u16>,
+}
+
+impl PolarGrid {
+    /// Create a maze with the given number of rings (including the centre). All Cells will be completely surrounded by walls.
+    pub fn new(rows: usize) -> Self {
+        let mut counts = Vec::with_capacity(rows);
+        let mut offsets = Vec::with_capacity(rows);
+        let mut size = 0;
+
+        for row in 0..rows {
+            let count = match row {
+                0 => 1,
+                _ => {
+                    // Split every Cell of the previous ring into however many Cells keep their width closest to their height.
+                    let previous = counts[row - 1];
+                    let circumference = 2.0 * PI * row as f64;
+                    let ratio = (circumference / previous as f64).round().max(1.0) as usize;
+                    previous * ratio
+                }
+            };
+
+            offsets.push(size);
+            counts.push(count);
+            size += count;
+        }
+
+        PolarGrid {
+            rows,
+            counts,
+            offsets,
+            links: vec![0; size],
+        }
+    }
+
+    /// Return the number of Cells in the given ring.
+    pub fn cells_in_row(&self, row: usize) -> usize {
+        self.counts[row]
+    }
+
+    /// Return the index of the Cell at position 'col' (clockwise) in ring 'row'.
+    pub fn index(&self, row: usize, col: usize) -> usize {
+        self.offsets[row] + col
+    }
+
+    /// Return the ring and position within the ring of the Cell at the given index.
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        let row = match self.offsets.binary_search(&index) {
+            Ok(row) => row,
+            Err(row) => row - 1,
+        };
+        (row, index - self.offsets[row])
+    }
+
+    /// Return the index of the Cell one ring closer to the centre that the Cell at the given index touches.
+    pub fn inward(&self, index: usize) -> Option<usize> {
+        let (row, col) = self.position(index);
+        if row == 0 {
+            return None;
+        }
+
+        let ratio = self.counts[row] / self.counts[row - 1];
+        Some(self.index(row - 1, col / ratio))
+    }
+
+    /// Return the indices of the Cells one ring further from the centre that the Cell at the given index touches.
+    pub fn outward(&self, index: usize) -> Vec<usize> {
+        let (row, col) = self.position(index);
+        if row + 1 >= self.rows {
+            return Vec::new();
+        }
+
+        let ratio = self.counts[row + 1] / self.counts[row];
+        (col * ratio..(col + 1) * ratio)
+            .map(|c| self.index(row + 1, c))
+            .collect()
+    }
+
+    /// Return the index of the next Cell clockwise in the same ring. The centre has no clockwise neighbour.
+    pub fn clockwise(&self, index: usize) -> Option<usize> {
+        let (row, col) = self.position(index);
+        match self.counts[row] {
+            1 => None,
+            count => Some(self.index(row, (col + 1) % count)),
+        }
+    }
+
+    /// Return the index of the next Cell counter-clockwise in the same ring. The centre has no counter-clockwise neighbour.
+    pub fn counter_clockwise(&self, index: usize) -> Option<usize> {
+        let (row, col) = self.position(index);
+        match self.counts[row] {
+            1 => None,
+            count => Some(self.index(row, (col + count - 1) % count)),
+        }
+    }
+
+    /// Return the position of neighbour 'b' in the neighbours of Cell 'a'. Panics if they aren't neighbours.
+    fn slot(&self, a: usize, b: usize) -> usize {
+        self.neighbours(a)
+            .iter()
+            .position(|&nb| nb == b)
+            .expect("cells are not neighbours")
+    }
+
+    /// Create an ImageBuffer from the maze. If 'colour' is true, Cells are coloured by their distance from the centre.
+    pub fn create_image(
+        &self,
+        cell_size: usize,
+        final_x: u32,
+        final_y: u32,
+        colour: bool,
+    ) -> image::RgbImage {
+        let distances = if colour {
+            self.distances(0)
+        } else {
+            Vec::new()
+        };
+
+        render_image(&distances, final_x, final_y, |cell_bg| {
+            self.draw(cell_size, cell_bg)
+        })
+    }
+
+    /// Draw the maze with each ring 'cell_size' pixels deep. 'cell_bg' gives the background colour of the Cell at each index.
+    /// Cells it returns None for are left white.
+    pub fn draw<F>(&self, cell_size: usize, cell_bg: F) -> image::RgbImage
+    where
+        F: Fn(usize) -> Option<image::Rgb<u8>>,
+    {
+        // Set image dimensions.
+        let img_size = 2 * cell_size * self.rows + 1;
+        let centre = (img_size / 2) as f64;
+        let size = cell_size as f64;
+
+        // Set colours.
+        let bg = image::Rgb([255, 255, 255]);
+        let wall = image::Rgb([0, 0, 0]);
+
+        // Create ImageBuffer.
+        let mut imgbuf = image::ImageBuffer::from_pixel(img_size as u32, img_size as u32, bg);
+
+        // Return the point at the given distance from the centre and angle clockwise from east.
+        let point = |radius: f64, theta: f64| {
+            (centre + radius * theta.cos(), centre + radius * theta.sin())
+        };
+
+        // Draw cell backgrounds. Arcs are approximated with a point for every pixel along them.
+        for index in 0..self.size() {
+            if let Some(cell_bg) = cell_bg(index) {
+                let (row, col) = self.position(index);
+                let theta = 2.0 * PI / self.counts[row] as f64;
+                let (inner, outer) = (row as f64 * size, (row + 1) as f64 * size);
+                let (ccw, cw) = (col as f64 * theta, (col + 1) as f64 * theta);
+
+                let steps = (outer * theta).ceil().max(1.0) as usize;
+                let mut points: Vec<_> = (0..=steps)
+                    .map(|i| point(outer, ccw + (cw - ccw) * i as f64 / steps as f64))
+                    .collect();
+                if row > 0 {
+                    points.extend(
+                        (0..=steps)
+                            .rev()
+                            .map(|i| point(inner, ccw + (cw - ccw) * i as f64 / steps as f64)),
+                    );
+                }
+                fill_polygon(&mut imgbuf, &points, cell_bg);
+            }
+        }
+
+        for index in 0..self.size() {
+            let (row, col) = self.position(index);
+            if row == 0 {
+                continue;
+            }
+
+            let theta = 2.0 * PI / self.counts[row] as f64;
+            let (inner, outer) = (row as f64 * size, (row + 1) as f64 * size);
+            let cw = (col + 1) as f64 * theta;
+
+            // Draw arc for the inward wall of the cell.
+            if !self.is_linked(index, self.inward(index).unwrap()) {
+                draw_arc(
+                    &mut imgbuf,
+                    (centre, centre),
+                    inner,
+                    col as f64 * theta,
+                    cw,
+                    wall,
+                );
+            }
+
+            // Draw line for the clockwise wall of the cell.
+            if !self.is_linked(index, self.clockwise(index).unwrap()) {
+                draw_line(&mut imgbuf, point(inner, cw), point(outer, cw), wall);
+            }
+        }
+
+        // Draw Boundary. Outermost ring.
+        let radius = self.rows as f64 * size;
+        draw_arc(&mut imgbuf, (centre, centre), radius, 0.0, 2.0 * PI, wall);
+        imgbuf
+    }
+}
+
+impl Grid for PolarGrid {
+    fn size(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Neighbours are ordered inward, clockwise, counter-clockwise and then outward (clockwise).
+    fn neighbours(&self, index: usize) -> Vec<usize> {
+        let mut neighbours: Vec<usize> = self.inward(index).into_iter().collect();
+        neighbours.extend(self.clockwise(index));
+        neighbours.extend(
+            self.counter_clockwise(index)
+                .filter(|&ccw| Some(ccw) != self.clockwise(index)),
+        );
+        neighbours.extend(self.outward(index));
+        neighbours
+    }
+
+    fn links(&self, index: usize) -> Vec<usize> {
+        self.neighbours(index)
+            .into_iter()
+            .enumerate()
+            .filter(|&(slot, _)| self.links[index] & (1 << slot) != 0)
+            .map(|(_, nb)| nb)
+            .collect()
+    }
+
+    fn is_linked(&self, a: usize, b: usize) -> bool {
+        match self.neighbours(a).iter().position(|&nb| nb == b) {
+            Some(slot) => self.links[a] & (1 << slot) != 0,
+            None => false,
+        }
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        let (slot_a, slot_b) = (self.slot(a, b), self.slot(b, a));
+        self.links[a] |= 1 << slot_a;
+        self.links[b] |= 1 << slot_b;
+    }
+
+    fn unlink(&mut self, a: usize, b: usize) {
+        let (slot_a, slot_b) = (self.slot(a, b), self.slot(b, a));
+        self.links[a] &= !(1 << slot_a);
+        self.links[b] &= !(1 << slot_b);
+    }
+
+    fn random_cell(&self, rng: &mut dyn RngCore) -> Option<usize> {
+        if self.links.is_empty() {
+            return None;
+        }
+        Some(rng.gen_range(0, self.links.len()))
+    }
+}
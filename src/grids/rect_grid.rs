@@ -1,82 +1,212 @@
 // External imports
-use colorous;
 use image;
-use rand::Rng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
 
 // Std imports
 use std::fmt;
-use std::rc::Rc;
 
 // Crate imports
-use crate::{Cell, Direction, Direction::*, HardCellLink};
+use crate::grids::draw::{draw_line, fill_polygon, render_image};
+use crate::{Direction, Direction::*, Distances, Grid, Mask};
 
-/// Represents a maze. Contains a Vector of Cells and provides methods for interacting with them.
+/// How a RectGrid joins its edges together. Cells on an edge that wraps are neighbours of the Cells on the opposite edge.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Wrap {
+    /// No edges wrap.
+    None,
+
+    /// The East and West edges are joined.
+    Cylinder,
+
+    /// The East and West edges are joined, and so are the North and South edges.
+    Torus,
+
+    /// The East and West edges are joined after flipping one of them upside down, so row 'r' meets row 'rows - 1 - r'.
+    Mobius,
+}
+
+// Set on a Cell with a tunnel running underneath it. The tunnel runs at right angles to the passage through the Cell.
+const UNDER: u8 = 0b1_0000;
+
+// Set on a Cell that has been culled from the maze. It is drawn as solid rock.
+const SOLID: u8 = 0b10_0000;
+
+/// Represents a maze. Stores the links of every Cell as a single byte and provides methods for interacting with them.
+/// Cells are identified by their index (row * cols + col). Neighbours are found with index arithmetic rather than pointers.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RectGrid {
     pub rows: usize,
     pub cols: usize,
-    pub grid: Vec<HardCellLink>,
+
+    // Link bits for every Cell. A Cell is linked in a Direction if 'Direction::bit' is set. Links are stored on both Cells.
+    links: Vec<u8>,
+
+    // Cells that are turned off in the Mask have no neighbours and are never part of the maze.
+    mask: Option<Mask>,
+
+    // Which edges of the maze are joined together.
+    wrap: Wrap,
+
+    // Whether passages can tunnel under straight corridors.
+    weave: bool,
 }
 
+// RectGrid holds nothing but plain data, so it can be generated on one thread and handed to another. Fail to compile if that changes.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<RectGrid>();
+};
+
 impl RectGrid {
-    /// Create a maze of given size. All Cells will be completely surroudned by walls.
+    /// Create a maze of given size. All Cells will be completely surrounded by walls.
     pub fn new(rows: usize, cols: usize) -> Self {
-        let mut grid = Vec::with_capacity(rows * cols);
+        RectGrid {
+            rows,
+            cols,
+            links: vec![0; rows * cols],
+            mask: None,
+            wrap: Wrap::None,
+            weave: false,
+        }
+    }
 
-        // Construct grid.
-        for _ in 0..cols * rows {
-            grid.push(Cell::new());
+    /// Create a maze in the shape of the Mask. Cells that are off in the Mask are left out of the maze.
+    pub fn with_mask(mask: Mask) -> Self {
+        RectGrid {
+            rows: mask.rows,
+            cols: mask.cols,
+            links: vec![0; mask.rows * mask.cols],
+            mask: Some(mask),
+            wrap: Wrap::None,
+            weave: false,
         }
+    }
 
-        // Create maze.
-        let rect_grid = RectGrid { rows, cols, grid };
+    /// Return the maze with its edges joined according to the Wrap. Should be called before any Cells are linked.
+    pub fn wrapped(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
 
-        // Point Cells to neighbours.
-        for (idx, cell) in rect_grid.grid.iter().enumerate() {
-            let mut cell = cell.borrow_mut();
+    /// Return how the edges of the maze are joined.
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
 
-            // For each direction, find neighbour in that Direction (Some or None) and point Cell there.
-            for d in vec![N, S, E, W] {
-                match rect_grid.get_cell_relative(idx, d) {
-                    Some(rc) => cell.neighbours.insert(d, Some(Rc::downgrade(&rc))),
-                    None => cell.neighbours.insert(d, None),
-                };
-            }
-        }
-        rect_grid
+    /// Return the maze with weaving turned on. Passages can then tunnel under a straight corridor to reach the Cell on the far side.
+    /// Tunnels are offered as extra neighbours of Cells that are already part of the maze, so Generators that link a Cell as soon as
+    /// they reach it (e.g. Backtracker, HuntAndKill and Prims) carve them. The maze is drawn with inset walls to show the crossings.
+    pub fn woven(mut self) -> Self {
+        self.weave = true;
+        self
+    }
+
+    /// Return true if weaving is turned on.
+    pub fn is_woven(&self) -> bool {
+        self.weave
+    }
+
+    /// Return true if a tunnel runs underneath the Cell at the given index.
+    pub fn is_under(&self, index: usize) -> bool {
+        self.links[index] & UNDER != 0
+    }
+
+    /// Return true if the Cell at the given index has been culled from the maze and is solid rock.
+    pub fn is_solid(&self, index: usize) -> bool {
+        self.links[index] & SOLID != 0
+    }
+
+    /// Return the Mask the maze was created with, if any.
+    pub fn mask(&self) -> Option<&Mask> {
+        self.mask.as_ref()
+    }
+
+    /// Return true if the maze is plain, with no Mask and no Wrap. Generators that rely on complete rows and columns of Cells
+    /// that end at the edges of the grid leave any other maze untouched.
+    pub fn is_plain(&self) -> bool {
+        self.mask.is_none() && self.wrap == Wrap::None
     }
 
-    /// Get a pointer to a random cell within the grid. Returns option since grid can be 0 by 0.
-    pub fn get_random_cell(&self) -> Option<HardCellLink> {
-        let mut rng = rand::thread_rng();
-        let row = rng.gen_range(0, self.rows);
-        let col = rng.gen_range(0, self.cols);
-        let cell = self.grid.get(row * self.cols + col)?;
-        Some(Rc::clone(&cell))
+    /// Create a maze of given size with every Cell linked to all of its neighbours. The maze is a single empty room.
+    pub fn new_open(rows: usize, cols: usize) -> Self {
+        let mut rect_grid = RectGrid::new(rows, cols);
+        rect_grid.link_all();
+        rect_grid
     }
-    /// Given the index of a cell in the maze, return the cell that exists at that index.
-    pub fn get_cell(&self, index: usize) -> Option<HardCellLink> {
-        let cell_ref = self.grid.get(index)?;
-        let cell_rc = Rc::clone(&cell_ref);
-        Some(cell_rc)
+
+    /// Return the number of Cells in the maze.
+    pub fn size(&self) -> usize {
+        self.links.len()
     }
 
-    /// Given the row number and column number, return the Cell that exists at that position in the slice (or return None).
-    pub fn get_cell_row_col(&self, row: usize, col: usize) -> Option<HardCellLink> {
-        let cell_ref = self.grid.get(row * self.cols + col)?;
-        let cell_rc = Rc::clone(&cell_ref);
-        Some(cell_rc)
+    /// Link every Cell to all of its neighbours, removing every interior wall. Returns the indices of the pair of Cells in every link made.
+    pub fn link_all(&mut self) -> Vec<(usize, usize)> {
+        let mut links = Vec::new();
+
+        for index in 0..self.size() {
+            // Links are made in both directions, so only the South and East walls of each Cell need removing.
+            for d in [S, E] {
+                if let Some(nb_index) = self.get_index_relative(index, d) {
+                    if !self.is_linked(index, d) {
+                        self.link(index, d);
+                        links.push((index, nb_index));
+                    }
+                }
+            }
+        }
+        links
     }
 
-    /// Given the index of the current cell and a direction, return a pointer to the Cell that exists in that direction. Return None if Cell that lies in that direction doesn't exist.
-    pub fn get_cell_relative(&self, idx: usize, d: Direction) -> Option<HardCellLink> {
-        let cell_ref = self.grid.get(self.get_index_relative(idx, d)?)?;
-        let cell_rc = Rc::clone(&cell_ref);
-        Some(cell_rc)
+    /// Get the index of a random cell within the grid using the given random number generator. Never picks a masked Cell.
+    /// Returns None if the grid is 0 by 0 or every Cell is masked.
+    pub fn get_random_index<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+        let on = self
+            .mask
+            .as_ref()
+            .map_or(self.links.len(), |mask| mask.count());
+        if on == 0 {
+            return None;
+        }
+
+        // Pick Cells until one that isn't masked is found.
+        loop {
+            let row = rng.gen_range(0, self.rows);
+            let col = rng.gen_range(0, self.cols);
+            let index = row * self.cols + col;
+
+            if !self.is_masked(index) {
+                return Some(index);
+            }
+        }
     }
 
     /// Given the index of the current cell and a direction, return the index of the Cell that exists in that direction. Return None if Cell in that direction doesn't exist.
+    /// Masked Cells have no neighbours and are never the neighbour of another Cell.
     pub fn get_index_relative(&self, idx: usize, d: Direction) -> Option<usize> {
-        match d {
+        if self.is_masked(idx) {
+            return None;
+        }
+
+        self.index_relative(idx, d)
+            .filter(|&nb_index| !self.is_masked(nb_index))
+    }
+
+    /// Return true if a wall should be drawn on the side of the Cell at the given index in the given Direction.
+    /// Walls are drawn wherever Cells aren't linked, except between two masked or solid Cells or a masked or solid Cell and the boundary.
+    /// Links across a wrapped edge leave a gap in the boundary on both sides of the maze.
+    fn has_wall(&self, index: usize, d: Direction) -> bool {
+        let is_open = |index: usize| !self.is_masked(index) && !self.is_solid(index);
+        let nb_open = match self.index_relative(index, d) {
+            Some(nb_index) => is_open(nb_index),
+            None => false,
+        };
+        !self.is_linked(index, d) && (is_open(index) || nb_open)
+    }
+
+    /// Return the index of the Cell in the given Direction, ignoring the Mask. Steps off the edge of the grid follow the Wrap.
+    fn index_relative(&self, idx: usize, d: Direction) -> Option<usize> {
+        let nb_index = match d {
             // Get index of cell to north. One row above (same as the number of columns back in a 1d array).
             N => idx.checked_sub(self.cols),
 
@@ -101,52 +231,339 @@ impl RectGrid {
                 }
                 _ => None,
             },
+        };
+
+        if nb_index.is_some() {
+            return nb_index;
+        }
+
+        // Wrap around the edge. Only dimensions longer than 2 wrap, otherwise the Cells on either side would be the same Cell.
+        let (row, col) = (idx / self.cols, idx % self.cols);
+        let (rows, cols) = (self.rows, self.cols);
+
+        match (self.wrap, d) {
+            (Wrap::Cylinder, E) | (Wrap::Torus, E) if cols > 2 => Some(row * cols),
+            (Wrap::Cylinder, W) | (Wrap::Torus, W) if cols > 2 => Some(row * cols + cols - 1),
+            (Wrap::Torus, N) if rows > 2 => Some((rows - 1) * cols + col),
+            (Wrap::Torus, S) if rows > 2 => Some(col),
+
+            // The strip is flipped before its ends are joined, so the top row meets the bottom row.
+            (Wrap::Mobius, E) if cols > 2 => Some((rows - 1 - row) * cols),
+            (Wrap::Mobius, W) if cols > 2 => Some((rows - 1 - row) * cols + cols - 1),
+            _ => None,
         }
     }
 
-    /// Return an Iterator over Maze. Provides each cell, one by one.
-    pub fn iter_cell(&self) -> IterCell {
-        IterCell::new(self)
+    /// Return the Directions of the neighbours of the Cell at the given index. Always ordered N, E, S, W.
+    pub fn get_neighbours(&self, index: usize) -> Vec<Direction> {
+        Direction::ALL
+            .iter()
+            .copied()
+            .filter(|&d| self.get_index_relative(index, d).is_some())
+            .collect()
     }
 
-    /// Return an Iterator over Maze. Provides each row, one by one.
-    pub fn iter_row(&self) -> IterRow {
-        IterRow::new(self)
+    /// Return the Directions of the neighbours of the Cell at the given index that have no links. Solid Cells have no links.
+    pub fn get_unlinked_neighbours(&self, index: usize) -> Vec<Direction> {
+        self.get_neighbours(index)
+            .into_iter()
+            .filter(|&d| {
+                self.get_linked(self.get_index_relative(index, d).unwrap())
+                    .is_empty()
+            })
+            .collect()
     }
 
-    /// Breadth first search to generate array of distances for colouring.
-    pub fn get_distances(&self) -> Vec<Option<usize>> {
-        let mut distances = vec![None; self.cols * self.rows];
+    /// Return the Directions of the neighbours of the Cell at the given index that have links.
+    pub fn get_linked_neighbours(&self, index: usize) -> Vec<Direction> {
+        self.get_neighbours(index)
+            .into_iter()
+            .filter(|&d| {
+                !self
+                    .get_linked(self.get_index_relative(index, d).unwrap())
+                    .is_empty()
+            })
+            .collect()
+    }
+
+    /// Return the Directions the Cell at the given index is linked to another Cell in. Always ordered N, E, S, W.
+    /// Gaps in the outer wall made by 'open_boundary' don't lead to a Cell and are left out, so they don't count towards dead ends.
+    pub fn get_linked(&self, index: usize) -> Vec<Direction> {
+        Direction::ALL
+            .iter()
+            .copied()
+            .filter(|&d| self.is_linked(index, d) && self.get_index_relative(index, d).is_some())
+            .collect()
+    }
 
-        // Pick start cell and place it in "current" vec
-        let cell_rc = self.get_cell(0).expect("no cell at index 0");
-        let mut current = vec![(cell_rc, 0)];
+    /// Return true if the Cell at the given index is linked to its neighbour in the given Direction.
+    pub fn is_linked(&self, index: usize, d: Direction) -> bool {
+        self.links[index] & d.bit() != 0
+    }
 
-        let mut distance = 0;
-        while !current.is_empty() {
-            let mut next = Vec::new();
+    /// Link the Cell at the given index with its neighbour in the given Direction (and the neighbour with the Cell).
+    pub fn link(&mut self, index: usize, d: Direction) {
+        let nb_index = self
+            .get_index_relative(index, d)
+            .expect("no neighbour to link to");
 
-            for (cell_rc, index) in &current {
-                distances[*index] = Some(distance);
+        // Linking a solid Cell carves it back out of the rock.
+        self.links[index] = (self.links[index] | d.bit()) & !SOLID;
+        self.links[nb_index] = (self.links[nb_index] | d.opposite().bit()) & !SOLID;
+    }
 
-                let cell = cell_rc.borrow();
-                let nb_dirs = cell.get_linked();
-                for dir in nb_dirs {
-                    let nb_index = self.get_index_relative(*index, *dir).unwrap();
-                    let nb_rc = cell.get_neighbour(*dir).unwrap();
+    /// Unlink the Cell at the given index from its neighbour in the given Direction (and the neighbour from the Cell).
+    pub fn unlink(&mut self, index: usize, d: Direction) {
+        let nb_index = self
+            .get_index_relative(index, d)
+            .expect("no neighbour to unlink from");
 
-                    if distances[nb_index].is_none() {
-                        next.push((nb_rc, nb_index));
-                    }
+        self.links[index] &= !d.bit();
+        self.links[nb_index] &= !d.opposite().bit();
+    }
+
+    /// Return the indices of every dead end in the maze. A dead end is a Cell with exactly one link.
+    pub fn dead_ends(&self) -> Vec<usize> {
+        (0..self.size())
+            .filter(|&index| self.get_linked(index).len() == 1)
+            .collect()
+    }
+
+    /// Remove dead ends to add loops to the maze. Every dead end is linked to one of its unlinked neighbours with probability 'p',
+    /// preferring neighbours that are dead ends too, so a single link removes two of them. A 'p' of 1.0 removes every dead end.
+    /// Returns the indices of the pair of Cells in every link made.
+    pub fn braid<R: Rng + ?Sized>(&mut self, p: f64, rng: &mut R) -> Vec<(usize, usize)> {
+        let mut links = Vec::new();
+        let mut dead_ends = self.dead_ends();
+        dead_ends.shuffle(rng);
+
+        for index in dead_ends {
+            // Linking an earlier dead end may already have removed this one. Culling can leave a dead end above a tunnel,
+            // and linking it would clash with the link bits of the tunnel.
+            if self.get_linked(index).len() != 1 || self.is_under(index) || rng.gen::<f64>() >= p {
+                continue;
+            }
+
+            // Cells with a tunnel underneath them must stay straight corridors and solid Cells stay out of the maze.
+            // A neighbour with its side already linked is the end of a tunnel running under this Cell.
+            let options: Vec<Direction> = self
+                .get_neighbours(index)
+                .into_iter()
+                .filter(|&d| !self.is_linked(index, d))
+                .filter(|&d| {
+                    let nb_index = self.get_index_relative(index, d).unwrap();
+                    !self.is_under(nb_index)
+                        && !self.is_solid(nb_index)
+                        && !self.is_linked(nb_index, d.opposite())
+                })
+                .collect();
+            let dead_end_options: Vec<Direction> = options
+                .iter()
+                .copied()
+                .filter(|&d| {
+                    let nb_index = self.get_index_relative(index, d).unwrap();
+                    self.get_linked(nb_index).len() == 1
+                })
+                .collect();
+
+            let choices = match dead_end_options.is_empty() {
+                true => &options,
+                false => &dead_end_options,
+            };
+            if let Some(&d) = choices.choose(rng) {
+                self.link(index, d);
+                links.push((index, self.get_index_relative(index, d).unwrap()));
+            }
+        }
+        links
+    }
+
+    /// Cull dead ends to make the maze sparse. Every iteration removes all of the current dead ends, unlinking them and
+    /// turning them into solid rock, which shortens every dead end corridor by one Cell. Stops after 'iterations' or as soon as
+    /// only 'keep' (a fraction between 0 and 1) of the Cells that aren't masked are left, whichever comes first.
+    /// Dead ends are removed in a random order so the final iteration doesn't favour any part of the maze.
+    /// Returns the indices of the pair of Cells in every link removed.
+    pub fn cull<R: Rng + ?Sized>(
+        &mut self,
+        iterations: usize,
+        keep: f64,
+        rng: &mut R,
+    ) -> Vec<(usize, usize)> {
+        let mut unlinks = Vec::new();
+        let mut open = (0..self.size())
+            .filter(|&index| !self.is_masked(index) && !self.is_solid(index))
+            .count();
+        let target = (open as f64 * keep).ceil() as usize;
+
+        for _ in 0..iterations {
+            let mut dead_ends = self.dead_ends();
+            if dead_ends.is_empty() || open <= target {
+                break;
+            }
+            dead_ends.shuffle(rng);
+
+            for index in dead_ends {
+                // Removing the Cell at the other end of a short corridor may have left this one as the last Cell standing.
+                // Cells with a tunnel underneath them are kept so the tunnel stays visible.
+                if open <= target || Grid::links(self, index).len() != 1 || self.is_under(index) {
+                    continue;
                 }
+
+                let nb_index = Grid::links(self, index)[0];
+                Grid::unlink(self, index, nb_index);
+                self.links[index] |= SOLID;
+                unlinks.push((index, nb_index));
+                open -= 1;
             }
-            distance += 1;
-            current = next;
         }
-        distances
+        unlinks
+    }
+
+    /// Breadth first search from the top left Cell (the first Cell that isn't masked or solid) to generate array of distances for colouring.
+    pub fn get_distances(&self) -> Vec<Option<usize>> {
+        match self.first_open_cell() {
+            Some(start) => self.distances(start),
+            None => vec![None; self.size()],
+        }
+    }
+
+    /// Return the index of the first Cell that isn't masked or solid.
+    fn first_open_cell(&self) -> Option<usize> {
+        (0..self.size()).find(|&index| !self.is_masked(index) && !self.is_solid(index))
+    }
+
+    /// Return the distance to every Cell from the Cell at (row, col), e.g. to colour the maze from its centre or entrance.
+    /// Panics if (row, col) is outside the maze.
+    pub fn get_distances_from(&self, row: usize, col: usize) -> Distances {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside the maze",
+            row,
+            col
+        );
+        Distances::new(self, &[row * self.cols + col])
+    }
+
+    /// Return the two ends of the longest path through the maze and the path itself, including both ends. Found with two breadth
+    /// first searches: the Cell farthest from any Cell is one end of the longest path and the Cell farthest from that is the other.
+    /// Returns None if every Cell is masked or solid.
+    pub fn longest_path(&self) -> Option<(usize, usize, Vec<usize>)> {
+        let start = self.first_open_cell()?;
+        let first = Distances::new(self, &[start]).farthest()?;
+        let distances = Distances::new(self, &[first]);
+        let last = distances.farthest()?;
+        Some((first, last, distances.path_to(self, last)))
+    }
+
+    /// Open the outer walls at the two ends of the longest path between Cells on the boundary of the maze, making them the
+    /// start and exit. Uses the same two breadth first searches as 'longest_path', only considering Cells on the boundary.
+    /// The ends can differ from the ones 'longest_path' returns, which may lie inside the maze.
+    /// Returns the indices of the start and exit, or None if the maze doesn't have two boundary Cells that are connected.
+    pub fn open_longest_path(&mut self) -> Option<(usize, usize)> {
+        let boundary: Vec<usize> = (0..self.size())
+            .filter(|&index| !self.is_solid(index) && self.boundary_direction(index).is_some())
+            .collect();
+
+        let start = *boundary.first()?;
+        let first = Distances::new(self, &[start]).farthest_of(&boundary)?;
+        let distances = Distances::new(self, &[first]);
+        let last = distances.farthest_of(&boundary)?;
+        if last == first {
+            return None;
+        }
+
+        self.open_boundary(first);
+        self.open_boundary(last);
+        Some((first, last))
+    }
+
+    /// Remove the outer wall of the Cell at the given index, leaving a gap in the boundary. Walls to masked Cells count as outer walls.
+    /// Returns the Direction of the wall removed, or None if the Cell isn't on the boundary.
+    pub fn open_boundary(&mut self, index: usize) -> Option<Direction> {
+        let d = self.boundary_direction(index)?;
+        self.links[index] |= d.bit();
+        Some(d)
+    }
+
+    /// Return the first Direction in which the Cell at the given index has an outer wall that is still closed.
+    fn boundary_direction(&self, index: usize) -> Option<Direction> {
+        if self.is_masked(index) {
+            return None;
+        }
+
+        Direction::ALL
+            .iter()
+            .copied()
+            .find(|&d| self.get_index_relative(index, d).is_none() && !self.is_linked(index, d))
+    }
+
+    /// Return the Direction of neighbour 'b' from Cell 'a' and, if 'b' is on the far side of a tunnel, the index of the Cell
+    /// the tunnel runs under. Panics if they aren't neighbours.
+    fn direction_between(&self, a: usize, b: usize) -> (Direction, Option<usize>) {
+        if let Some(&d) = Direction::ALL
+            .iter()
+            .find(|&&d| self.get_index_relative(a, d) == Some(b))
+        {
+            return (d, None);
+        }
+
+        Direction::ALL
+            .iter()
+            .find_map(|&d| {
+                let under = self.get_index_relative(a, d)?;
+                match self.get_index_relative(under, d) {
+                    Some(nb_index) if nb_index == b => Some((d, Some(under))),
+                    _ => None,
+                }
+            })
+            .expect("cells are not neighbours")
+    }
+
+    /// Return the index of the Cell the Cell at the given index is linked to in the given Direction, following tunnels.
+    fn linked_relative(&self, index: usize, d: Direction) -> Option<usize> {
+        if !self.is_linked(index, d) {
+            return None;
+        }
+
+        // The neighbour is linked back unless the link is a tunnel running underneath it.
+        let nb_index = self.get_index_relative(index, d)?;
+        if self.is_linked(nb_index, d.opposite()) || !self.is_under(nb_index) {
+            Some(nb_index)
+        } else {
+            self.get_index_relative(nb_index, d)
+        }
+    }
+
+    /// Return true if the Cells at the two indices are next to each other, ignoring tunnels.
+    pub(crate) fn is_direct_neighbour(&self, a: usize, b: usize) -> bool {
+        Direction::ALL
+            .iter()
+            .any(|&d| self.get_index_relative(a, d) == Some(b))
+    }
+
+    /// Return the index of the Cell a tunnel from the Cell at the given index could reach in the given Direction.
+    /// A tunnel can run under a neighbour that is a straight corridor at right angles to it, and only starts from a Cell
+    /// that is already part of the maze. On a wrapped side only 2 or 3 Cells long the far end is the Cell itself or one of
+    /// its direct neighbours, so no tunnel can be told apart from a plain link there.
+    fn tunnel_relative(&self, index: usize, d: Direction) -> Option<usize> {
+        if !self.weave || self.links[index] == 0 {
+            return None;
+        }
+
+        let under = self.get_index_relative(index, d)?;
+        let corridor = match d {
+            N | S => E.bit() | W.bit(),
+            E | W => N.bit() | S.bit(),
+        };
+
+        if self.links[under] != corridor {
+            return None;
+        }
+        self.get_index_relative(under, d)
+            .filter(|&far| far != index && !self.is_direct_neighbour(index, far))
     }
 
-    /// Create an ImageBuffer from the maze.
+    /// Create an ImageBuffer from the maze. If 'colour' is true, Cells are coloured by their distance from the top left Cell.
     pub fn create_image(
         &self,
         cell_size: usize,
@@ -154,41 +571,68 @@ impl RectGrid {
         final_y: u32,
         colour: bool,
     ) -> image::RgbImage {
+        // Calculate distances.
+        let distances = if colour {
+            self.get_distances()
+        } else {
+            Vec::new()
+        };
+
+        render_image(&distances, final_x, final_y, |cell_bg| {
+            self.draw(cell_size, cell_bg)
+        })
+    }
+
+    /// Create an ImageBuffer from the maze with Cells coloured by the given Distances. Cells without a distance are left white.
+    pub fn create_image_with_distances(
+        &self,
+        cell_size: usize,
+        final_x: u32,
+        final_y: u32,
+        distances: &Distances,
+    ) -> image::RgbImage {
+        render_image(distances.as_slice(), final_x, final_y, |cell_bg| {
+            self.draw(cell_size, cell_bg)
+        })
+    }
+
+    /// Draw the maze with each Cell taking up 'cell_size' pixels. 'cell_bg' gives the background colour of the Cell at each index.
+    /// Cells it returns None for are left white.
+    pub fn draw<F>(&self, cell_size: usize, cell_bg: F) -> image::RgbImage
+    where
+        F: Fn(usize) -> Option<image::Rgb<u8>>,
+    {
+        if self.weave {
+            return self.draw_woven(cell_size, cell_bg);
+        }
+
         // Set image dimensions.
-        let img_x = cell_size * self.rows + 1;
-        let img_y = cell_size * self.cols + 1;
+        let img_x = cell_size * self.cols + 1;
+        let img_y = cell_size * self.rows + 1;
 
         // Set colours.
         let bg = image::Rgb([255, 255, 255]);
         let wall = image::Rgb([0, 0, 0]);
-
-        // Calculate distances.
-        let cycles = 1;
-        let mut max = 0;
-        let mut distances = Vec::new();
-        let gradient = colorous::MAGMA;
-
-        if colour {
-            distances = self.get_distances();
-            max = 1 + distances.iter().max().unwrap().unwrap() / cycles;
-        }
+        let rock = image::Rgb([70, 70, 70]);
 
         // Create ImageBuffer.
         let mut imgbuf = image::ImageBuffer::from_pixel(img_x as u32, img_y as u32, bg);
 
-        if colour {
-            for row in 0..self.rows {
-                for col in 0..self.cols {
-                    let x1 = col * cell_size;
-                    let y1 = row * cell_size;
-                    let x2 = (col + 1) * cell_size;
-                    let y2 = (row + 1) * cell_size;
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let index = row * self.cols + col;
 
-                    // Draw cell background.
-                    let distance = distances[row * self.cols + col].unwrap();
-                    let colour = gradient.eval_rational(max - (distance % max), max);
-                    let cell_bg = image::Rgb([colour.r, colour.g, colour.b]);
+                let x1 = col * cell_size;
+                let y1 = row * cell_size;
+                let x2 = (col + 1) * cell_size;
+                let y2 = (row + 1) * cell_size;
 
+                // Draw cell background. Solid Cells are filled with rock.
+                let cell_bg = match self.is_solid(index) {
+                    true => Some(rock),
+                    false => cell_bg(index),
+                };
+                if let Some(cell_bg) = cell_bg {
                     for x in x1..x2 {
                         for y in y1..y2 {
                             imgbuf.put_pixel(x as u32, y as u32, cell_bg);
@@ -200,131 +644,218 @@ impl RectGrid {
 
         for row in 0..self.rows {
             for col in 0..self.cols {
-                let cell_rc = self.get_cell_row_col(row, col).unwrap();
-                let cell = cell_rc.borrow_mut();
+                let index = row * self.cols + col;
 
                 let x1 = col * cell_size;
                 let y1 = row * cell_size;
                 let x2 = (col + 1) * cell_size;
                 let y2 = (row + 1) * cell_size;
 
-                // Draw Boundary. West wall.
-                for y in 0..self.cols * cell_size + 1 {
-                    imgbuf.put_pixel(0, y as u32, wall);
-                }
-
-                // Draw Boundary. South wall.
-                for y in 0..self.rows * cell_size + 1 {
-                    imgbuf.put_pixel(y as u32, (self.rows * cell_size) as u32, wall);
-                }
-
                 // Draw line for North wall of cell. From (x1, y1) to (x2, y1).
-                if !cell.is_linked(N) {
+                if self.has_wall(index, N) {
                     for x in x1..x2 + 1 {
                         imgbuf.put_pixel(x as u32, y1 as u32, wall);
                     }
                 }
 
                 // Draw line for East wall of cell. (from x2, y1) to (x2, y2).
-                if !cell.is_linked(E) {
+                if self.has_wall(index, E) {
                     for y in y1..y2 + 1 {
                         imgbuf.put_pixel(x2 as u32, y as u32, wall);
                     }
                 }
+
+                // Draw Boundary. West wall of cells in the first column. From (x1, y1) to (x1, y2).
+                if col == 0 && self.has_wall(index, W) {
+                    for y in y1..y2 + 1 {
+                        imgbuf.put_pixel(x1 as u32, y as u32, wall);
+                    }
+                }
+
+                // Draw Boundary. South wall of cells in the last row. From (x1, y2) to (x2, y2).
+                if row + 1 == self.rows && self.has_wall(index, S) {
+                    for x in x1..x2 + 1 {
+                        imgbuf.put_pixel(x as u32, y2 as u32, wall);
+                    }
+                }
             }
         }
-        image::imageops::resize(
-            &imgbuf,
-            final_x,
-            final_y,
-            image::imageops::FilterType::Nearest,
-        )
+        imgbuf
     }
-}
 
-/// Cell Iterator for the Maze.
-pub struct IterCell<'a> {
-    maze: &'a RectGrid,
-    index: Option<usize>,
-}
+    /// Draw a woven maze. Walls are inset from the edge of each Cell so passages become corridors between them,
+    /// and tunnels show up as corridors that disappear under the Cell they cross.
+    fn draw_woven<F>(&self, cell_size: usize, cell_bg: F) -> image::RgbImage
+    where
+        F: Fn(usize) -> Option<image::Rgb<u8>>,
+    {
+        // Set image dimensions.
+        let img_x = cell_size * self.cols + 1;
+        let img_y = cell_size * self.rows + 1;
 
-impl<'a> IterCell<'a> {
-    pub fn new(maze: &'a RectGrid) -> Self {
-        IterCell { maze, index: None }
-    }
-}
+        // Set colours.
+        let bg = image::Rgb([255, 255, 255]);
+        let wall = image::Rgb([0, 0, 0]);
+        let rock = image::Rgb([70, 70, 70]);
+
+        // Create ImageBuffer.
+        let mut imgbuf = image::ImageBuffer::from_pixel(img_x as u32, img_y as u32, bg);
+
+        let size = cell_size as f64;
+        let inset = (size * 0.15).round().max(1.0);
+
+        for index in (0..self.size()).filter(|&index| !self.is_masked(index)) {
+            // Outer edges (x1, x4) and inset walls (x2, x3) of the cell, and the same for y.
+            let x1 = (index % self.cols) as f64 * size;
+            let y1 = (index / self.cols) as f64 * size;
+            let (x2, x3, x4) = (x1 + inset, x1 + size - inset, x1 + size);
+            let (y2, y3, y4) = (y1 + inset, y1 + size - inset, y1 + size);
+
+            // Solid Cells are filled with rock and have no walls of their own.
+            if self.is_solid(index) {
+                let corners = [(x1, y1), (x4, y1), (x4, y4), (x1, y4)];
+                fill_polygon(&mut imgbuf, &corners, rock);
+                continue;
+            }
+
+            // Rectangles covering the middle of the cell and the corridor leading out of it in each Direction.
+            let middle = ((x2, y2), (x3, y3));
+            let corridor = |d: Direction| match d {
+                N => ((x2, y1), (x3, y2)),
+                E => ((x3, y2), (x4, y3)),
+                S => ((x2, y3), (x3, y4)),
+                W => ((x1, y2), (x2, y3)),
+            };
+
+            // The two walls running along the sides of the corridor in each Direction.
+            let sides = |d: Direction| {
+                let ((left, top), (right, bottom)) = corridor(d);
+                match d {
+                    N | S => [
+                        ((left, top), (left, bottom)),
+                        ((right, top), (right, bottom)),
+                    ],
+                    E | W => [
+                        ((left, top), (right, top)),
+                        ((left, bottom), (right, bottom)),
+                    ],
+                }
+            };
+
+            // The inset wall closing off the middle of the cell in each Direction.
+            let inset_wall = |d: Direction| match d {
+                N => ((x2, y2), (x3, y2)),
+                E => ((x3, y2), (x3, y3)),
+                S => ((x2, y3), (x3, y3)),
+                W => ((x2, y2), (x2, y3)),
+            };
+
+            // Draw cell background.
+            if let Some(cell_bg) = cell_bg(index) {
+                let mut areas = vec![middle];
+                areas.extend(
+                    Direction::ALL
+                        .iter()
+                        .filter(|&&d| self.is_linked(index, d))
+                        .map(|&d| corridor(d)),
+                );
+
+                for ((left, top), (right, bottom)) in areas {
+                    let corners = [(left, top), (right, top), (right, bottom), (left, bottom)];
+                    fill_polygon(&mut imgbuf, &corners, cell_bg);
+                }
+            }
+
+            // Draw the sides of the corridor for linked Directions and an inset wall across the others.
+            for &d in &Direction::ALL {
+                let lines = match self.is_linked(index, d) {
+                    true => sides(d).to_vec(),
+                    false => vec![inset_wall(d)],
+                };
+
+                for (from, to) in lines {
+                    draw_line(&mut imgbuf, from, to, wall);
+                }
+            }
 
-impl<'a> Iterator for IterCell<'a> {
-    type Item = HardCellLink;
+            // Draw the sides of the tunnel on either side of the cell. It runs at right angles to the passage through the cell.
+            if self.is_under(index) {
+                let tunnel = match self.is_linked(index, N) {
+                    true => [W, E],
+                    false => [N, S],
+                };
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index.is_none() {
-            self.index = Some(0)
+                for (from, to) in tunnel.iter().flat_map(|&d| sides(d).to_vec()) {
+                    draw_line(&mut imgbuf, from, to, wall);
+                }
+            }
         }
-        let cell_ref = self.maze.grid.get(self.index.unwrap())?;
-        let cell_rc = Rc::clone(cell_ref);
-        self.index = Some(self.index.unwrap().checked_add(1)?);
-        Some(cell_rc)
+        imgbuf
     }
 }
 
-impl<'a> DoubleEndedIterator for IterCell<'a> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.index.is_none() {
-            self.index = Some(self.maze.grid.len() - 1);
-        }
-
-        let cell_ref = self.maze.grid.get(self.index.unwrap())?;
-        let cell_rc = Rc::clone(cell_ref);
-        self.index = Some(self.index.unwrap().checked_sub(1)?);
-        Some(cell_rc)
+impl Grid for RectGrid {
+    fn size(&self) -> usize {
+        self.links.len()
     }
-}
 
-/// Row Iterator for the Maze.
-pub struct IterRow<'a> {
-    maze: &'a RectGrid,
-    row: Option<usize>,
-}
+    /// Neighbours are ordered N, E, S, W, followed by any Cells that can be reached by tunnelling N, E, S or W.
+    fn neighbours(&self, index: usize) -> Vec<usize> {
+        let neighbours = Direction::ALL
+            .iter()
+            .filter_map(|&d| self.get_index_relative(index, d));
+        let tunnels = Direction::ALL
+            .iter()
+            .filter_map(|&d| self.tunnel_relative(index, d));
 
-impl<'a> IterRow<'a> {
-    pub fn new(maze: &'a RectGrid) -> Self {
-        IterRow { maze, row: None }
+        neighbours.chain(tunnels).collect()
     }
-}
 
-impl<'a> Iterator for IterRow<'a> {
-    type Item = &'a [HardCellLink];
+    fn links(&self, index: usize) -> Vec<usize> {
+        Direction::ALL
+            .iter()
+            .filter_map(|&d| self.linked_relative(index, d))
+            .collect()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.row.is_none() {
-            self.row = Some(0);
-        }
+    fn is_linked(&self, a: usize, b: usize) -> bool {
+        Direction::ALL
+            .iter()
+            .any(|&d| self.linked_relative(a, d) == Some(b))
+    }
 
-        if self.row.unwrap() < self.maze.rows {
-            let start = self.row.unwrap() * self.maze.cols;
-            let end = start + self.maze.cols;
-            self.row = Some(self.row.unwrap() + 1);
-            Some(&self.maze.grid[start..end])
-        } else {
-            None
+    fn link(&mut self, a: usize, b: usize) {
+        let (d, under) = self.direction_between(a, b);
+        match under {
+            None => self.link(a, d),
+            Some(under) => {
+                self.links[a] = (self.links[a] | d.bit()) & !SOLID;
+                self.links[b] = (self.links[b] | d.opposite().bit()) & !SOLID;
+                self.links[under] |= UNDER;
+            }
         }
     }
-}
 
-impl<'a> DoubleEndedIterator for IterRow<'a> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.row.is_none() {
-            self.row = Some(self.maze.rows);
+    fn unlink(&mut self, a: usize, b: usize) {
+        let (d, under) = self.direction_between(a, b);
+        match under {
+            None => self.unlink(a, d),
+            Some(under) => {
+                self.links[a] &= !d.bit();
+                self.links[b] &= !d.opposite().bit();
+                self.links[under] &= !UNDER;
+            }
         }
+    }
 
-        if self.row.unwrap() > 0 {
-            let start = (self.row.unwrap() - 1) * self.maze.cols;
-            let end = start + self.maze.cols;
-            self.row = Some(self.row.unwrap() - 1);
-            Some(&self.maze.grid[start..end])
-        } else {
-            None
+    fn random_cell(&self, rng: &mut dyn RngCore) -> Option<usize> {
+        self.get_random_index(rng)
+    }
+
+    fn is_masked(&self, index: usize) -> bool {
+        match &self.mask {
+            Some(mask) => !mask.is_on(index / self.cols, index % self.cols),
+            None => false,
         }
     }
 }
@@ -332,34 +863,48 @@ impl<'a> DoubleEndedIterator for IterRow<'a> {
 /// Implement Display trait for RectGrid. Creates (and outputs) string representation of the maze.
 impl fmt::Display for RectGrid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut output = "+".to_string() + &"---+".repeat(self.cols) + "\n";
+        let mut output = "+".to_string();
+        for index in 0..self.cols {
+            output.push_str(if self.has_wall(index, N) {
+                "---+"
+            } else {
+                "   +"
+            });
+        }
+        output.push('\n');
 
-        for i in (0..self.rows * self.cols).step_by(self.cols) {
-            let mut top = "|".to_string();
+        for row in 0..self.rows {
+            let mut top = match self.has_wall(row * self.cols, W) {
+                true => "|".to_string(),
+                false => " ".to_string(),
+            };
             let mut bot = "+".to_string();
             let corner = "+";
 
-            for cell in &self.grid[i..i + self.cols] {
-                let body = "   ";
+            for index in row * self.cols..(row + 1) * self.cols {
+                let body = match self.is_solid(index) {
+                    true => "###",
+                    false => "   ",
+                };
 
-                let east_boundary = match cell.borrow_mut().is_linked(E) {
-                    true => " ",
-                    false => "|",
+                let east_boundary = match self.has_wall(index, E) {
+                    true => "|",
+                    false => " ",
                 };
 
                 top.push_str(body);
                 top.push_str(east_boundary);
 
-                let south_boundary = match cell.borrow_mut().is_linked(S) {
-                    true => "   ",
-                    false => "---",
+                let south_boundary = match self.has_wall(index, S) {
+                    true => "---",
+                    false => "   ",
                 };
 
                 bot.push_str(south_boundary);
                 bot.push_str(corner);
             }
-            top.push_str("\n");
-            bot.push_str("\n");
+            top.push('\n');
+            bot.push('\n');
 
             output.push_str(&top);
             output.push_str(&bot);
@@ -0,0 +1,272 @@
+// External imports
+use image;
+use rand::{Rng, RngCore};
+
+// Standard imports
+use std::fmt::Write;
+
+// Self imports
+use crate::grids::draw::{distance_colours, draw_line, fill_polygon, render_image};
+use crate::Grid;
+
+// Link bits for the three sides of a triangle. The vertical side is the flat base, to the south of upright triangles
+// and to the north of upside down ones.
+const WEST: u8 = 0b001;
+const EAST: u8 = 0b010;
+const VERTICAL: u8 = 0b100;
+
+type Point = (f64, f64);
+
+/// A maze made of triangles. Cells are identified by their index (row * cols + col) like a RectGrid.
+/// Cells alternate between pointing up and pointing down, so every Cell has a neighbour to the east, west and either south or north.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriangleGrid {
+    pub rows: usize,
+    pub cols: usize,
+
+    // Link bits for every Cell, one for each side. Links are stored on both Cells.
+    links: Vec<u8>,
+}
+
+impl TriangleGrid {
+    /// Create a maze of given size. All Cells will be completely surrounded by walls.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        TriangleGrid {
+            rows,
+            cols,
+            links: vec![0; rows * cols],
+        }
+    }
+
+    /// Return true if the Cell at the given index points up. The top left Cell points up.
+    pub fn is_upright(&self, index: usize) -> bool {
+        (index / self.cols + index % self.cols).is_multiple_of(2)
+    }
+
+    /// Return the index of the neighbour on the given side of the Cell at the given index. Return None if it doesn't exist.
+    fn neighbour(&self, index: usize, side: u8) -> Option<usize> {
+        let (row, col) = (index / self.cols, index % self.cols);
+
+        match side {
+            WEST if col > 0 => Some(index - 1),
+            EAST if col + 1 < self.cols => Some(index + 1),
+            VERTICAL if self.is_upright(index) && row + 1 < self.rows => Some(index + self.cols),
+            VERTICAL if !self.is_upright(index) && row > 0 => Some(index - self.cols),
+            _ => None,
+        }
+    }
+
+    /// Return the side of Cell 'a' that neighbour 'b' is on. Panics if they aren't neighbours.
+    fn side_between(&self, a: usize, b: usize) -> u8 {
+        *[WEST, EAST, VERTICAL]
+            .iter()
+            .find(|&&side| self.neighbour(a, side) == Some(b))
+            .expect("cells are not neighbours")
+    }
+
+    /// Return the corners of the Cell at the given index, starting from the point of the triangle and going clockwise.
+    fn corners(&self, cell_size: usize, index: usize) -> [Point; 3] {
+        let (row, col) = (index / self.cols, index % self.cols);
+        let half_width = cell_size as f64 / 2.0;
+        let height = cell_size as f64 * 3f64.sqrt() / 2.0;
+
+        let cx = half_width + col as f64 * half_width;
+        let (top, bottom) = (row as f64 * height, (row + 1) as f64 * height);
+
+        if self.is_upright(index) {
+            [
+                (cx, top),
+                (cx + half_width, bottom),
+                (cx - half_width, bottom),
+            ]
+        } else {
+            [(cx, bottom), (cx - half_width, top), (cx + half_width, top)]
+        }
+    }
+
+    /// Return the walls the Cell at the given index is responsible for drawing, as pairs of points.
+    /// Walls between two Cells are drawn by the Cell on their west side (or north side for the vertical side).
+    fn walls(&self, cell_size: usize, index: usize) -> Vec<(Point, Point)> {
+        let corners = self.corners(cell_size, index);
+        let mut walls = Vec::new();
+
+        // The east and west sides join the point of the triangle to either end of its base.
+        let (east, west) = match self.is_upright(index) {
+            true => ((corners[0], corners[1]), (corners[2], corners[0])),
+            false => ((corners[0], corners[2]), (corners[1], corners[0])),
+        };
+        let base = (corners[1], corners[2]);
+
+        if self.neighbour(index, WEST).is_none() {
+            walls.push(west);
+        }
+        if self.links[index] & EAST == 0 {
+            walls.push(east);
+        }
+
+        // Upright Cells draw their south side. Upside down Cells only draw their north side on the boundary.
+        let owns_base = self.is_upright(index) || self.neighbour(index, VERTICAL).is_none();
+        if owns_base && self.links[index] & VERTICAL == 0 {
+            walls.push(base);
+        }
+        walls
+    }
+
+    /// Return the width and height of a drawing of the maze.
+    fn dimensions(&self, cell_size: usize) -> (f64, f64) {
+        let width = cell_size as f64 * (self.cols + 1) as f64 / 2.0;
+        let height = cell_size as f64 * 3f64.sqrt() / 2.0 * self.rows as f64;
+        (width, height)
+    }
+
+    /// Create an ImageBuffer from the maze. If 'colour' is true, Cells are coloured by their distance from the top left Cell.
+    pub fn create_image(
+        &self,
+        cell_size: usize,
+        final_x: u32,
+        final_y: u32,
+        colour: bool,
+    ) -> image::RgbImage {
+        let distances = if colour {
+            self.distances(0)
+        } else {
+            Vec::new()
+        };
+
+        render_image(&distances, final_x, final_y, |cell_bg| {
+            self.draw(cell_size, cell_bg)
+        })
+    }
+
+    /// Draw the maze with each triangle 'cell_size' pixels wide. 'cell_bg' gives the background colour of the Cell at each index.
+    /// Cells it returns None for are left white.
+    pub fn draw<F>(&self, cell_size: usize, cell_bg: F) -> image::RgbImage
+    where
+        F: Fn(usize) -> Option<image::Rgb<u8>>,
+    {
+        // Set image dimensions.
+        let (width, height) = self.dimensions(cell_size);
+        let img_x = width.ceil() as u32 + 1;
+        let img_y = height.ceil() as u32 + 1;
+
+        // Set colours.
+        let bg = image::Rgb([255, 255, 255]);
+        let wall = image::Rgb([0, 0, 0]);
+
+        // Create ImageBuffer.
+        let mut imgbuf = image::ImageBuffer::from_pixel(img_x, img_y, bg);
+
+        // Draw cell backgrounds.
+        for index in 0..self.size() {
+            if let Some(cell_bg) = cell_bg(index) {
+                fill_polygon(&mut imgbuf, &self.corners(cell_size, index), cell_bg);
+            }
+        }
+
+        for index in 0..self.size() {
+            for (from, to) in self.walls(cell_size, index) {
+                draw_line(&mut imgbuf, from, to, wall);
+            }
+        }
+        imgbuf
+    }
+
+    /// Create an SVG document of the maze with each triangle 'cell_size' units wide.
+    /// If 'colour' is true, Cells are coloured by their distance from the top left Cell.
+    pub fn create_svg(&self, cell_size: usize, colour: bool) -> String {
+        let (width, height) = self.dimensions(cell_size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"-1 -1 {w} {h}\">\n",
+            w = width.ceil() + 2.0,
+            h = height.ceil() + 2.0,
+        );
+        svg.push_str("<rect x=\"-1\" y=\"-1\" width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+        // Draw cell backgrounds.
+        if colour {
+            let distances = self.distances(0);
+            let colours = distance_colours(&distances);
+
+            for index in 0..self.size() {
+                if let Some(image::Rgb([r, g, b])) = colours(index) {
+                    let points: Vec<String> = self
+                        .corners(cell_size, index)
+                        .iter()
+                        .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+                        .collect();
+                    writeln!(
+                        svg,
+                        "<polygon points=\"{}\" fill=\"rgb({},{},{})\"/>",
+                        points.join(" "),
+                        r,
+                        g,
+                        b
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        // Draw walls.
+        svg.push_str("<g stroke=\"black\" stroke-width=\"1\" stroke-linecap=\"round\">\n");
+        for index in 0..self.size() {
+            for ((x1, y1), (x2, y2)) in self.walls(cell_size, index) {
+                writeln!(
+                    svg,
+                    "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\"/>",
+                    x1, y1, x2, y2
+                )
+                .unwrap();
+            }
+        }
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+}
+
+impl Grid for TriangleGrid {
+    fn size(&self) -> usize {
+        self.links.len()
+    }
+
+    /// Neighbours are ordered west, east and then north or south.
+    fn neighbours(&self, index: usize) -> Vec<usize> {
+        [WEST, EAST, VERTICAL]
+            .iter()
+            .filter_map(|&side| self.neighbour(index, side))
+            .collect()
+    }
+
+    fn links(&self, index: usize) -> Vec<usize> {
+        [WEST, EAST, VERTICAL]
+            .iter()
+            .filter(|&&side| self.links[index] & side != 0)
+            .filter_map(|&side| self.neighbour(index, side))
+            .collect()
+    }
+
+    fn is_linked(&self, a: usize, b: usize) -> bool {
+        [WEST, EAST, VERTICAL]
+            .iter()
+            .any(|&side| self.links[a] & side != 0 && self.neighbour(a, side) == Some(b))
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        let (side_a, side_b) = (self.side_between(a, b), self.side_between(b, a));
+        self.links[a] |= side_a;
+        self.links[b] |= side_b;
+    }
+
+    fn unlink(&mut self, a: usize, b: usize) {
+        let (side_a, side_b) = (self.side_between(a, b), self.side_between(b, a));
+        self.links[a] &= !side_a;
+        self.links[b] &= !side_b;
+    }
+
+    fn random_cell(&self, rng: &mut dyn RngCore) -> Option<usize> {
+        if self.links.is_empty() {
+            return None;
+        }
+        Some(rng.gen_range(0, self.links.len()))
+    }
+}
@@ -1,20 +1,39 @@
-mod cells;
+mod animation;
 mod gen;
 mod grids;
 
 // Directions
-pub use cells::cell::{Cell, Direction, HardCellLink, SoftCellLink};
+pub use grids::direction::{Direction, Direction3D, HexDirection};
+pub use grids::distances::Distances;
 
-// Maze struct
-pub use grids::rect_grid::RectGrid;
+// Maze structs
+pub use grids::grid::Grid;
+pub use grids::grid_3d::Grid3D;
+pub use grids::hex_grid::HexGrid;
+pub use grids::mask::Mask;
+pub use grids::polar_grid::PolarGrid;
+pub use grids::rect_grid::{RectGrid, Wrap};
+pub use grids::triangle_grid::TriangleGrid;
 
 // Generator trait
 pub use gen::generator::Generator;
+pub use gen::step::{Step, Steps};
+
+// Animation
+pub use animation::gif::{animate, Animation};
 
 // Algorithms
 pub use gen::aldous_broder::AldousBroder;
 pub use gen::backtracker::Backtracker;
-pub use gen::binary_tree::BinaryTree;
+pub use gen::binary_tree::{Bias, BinaryTree};
+pub use gen::ellers::Ellers;
+pub use gen::growing_tree::{GrowingTree, Selector};
+pub use gen::houston::Houston;
 pub use gen::hunt_and_kill::HuntAndKill;
+pub use gen::kruskal::Kruskal;
+pub use gen::prims::{Prims, PrimsMode};
+pub use gen::recursive_division::RecursiveDivision;
 pub use gen::sidewinder::Sidewinder;
+pub use gen::wall_wilsons::WallWilsons;
+pub use gen::weave_kruskal::WeaveKruskal;
 pub use gen::wilsons::Wilsons;